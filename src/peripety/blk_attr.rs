@@ -0,0 +1,111 @@
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use std::fs;
+use std::path::Path;
+
+// Block attributes from sysfs, no IO will be sent to block.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BlkAttributes {
+    pub size: u64, // in bytes
+    pub vendor: String,
+    pub model: String,
+    pub revision: String,
+    pub serial: String,
+    pub rotational: bool,
+    pub logical_block_size: u64,
+    pub physical_block_size: u64,
+    pub zoned: String, // none, host-aware or host-managed
+}
+
+// Missing attribute is treated as empty string as not all block types
+// provide them.
+fn read_attr(path: &str) -> String {
+    if !Path::new(path).exists() {
+        return String::new();
+    }
+    match Sysfs::read(path) {
+        Ok(s) => s.trim().to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn read_attr_u64(path: &str) -> u64 {
+    read_attr(path).parse::<u64>().unwrap_or(0)
+}
+
+// Return the first attribute found.
+fn read_attr_any(dir: &str, names: &[&str]) -> String {
+    for name in names {
+        let s = read_attr(&format!("{}/{}", dir, name));
+        if !s.is_empty() {
+            return s;
+        }
+    }
+    String::new()
+}
+
+// SCSI unit serial number VPD page: 4 bytes header followed by the serial.
+fn scsi_vpd_serial(dev_dir: &str) -> String {
+    match fs::read(format!("{}/vpd_pg80", dev_dir)) {
+        Ok(ref data) if data.len() > 4 => {
+            String::from_utf8_lossy(&data[4..])
+                .trim_matches(|c: char| c.is_whitespace() || c == '\0')
+                .to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+// Support query on these formats:
+//  * sda, sda1, dm-0, nvme0n1 (kernel name of block)
+pub(crate) fn blk_attributes_get(
+    blk: &str,
+) -> Result<BlkAttributes, PeripetyError> {
+    let sysfs_path = format!("/sys/class/block/{}", blk);
+    if !Path::new(&sysfs_path).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "Block {} does not exists",
+            blk
+        )));
+    }
+    // Partition share queue and device attributes with its disk.
+    let mut disk_path = sysfs_path.clone();
+    if Path::new(&format!("{}/partition", sysfs_path)).exists() {
+        if let Ok(p) = Path::new(&sysfs_path).canonicalize() {
+            if let Some(p) = p.parent().and_then(|p| p.to_str()) {
+                disk_path = p.to_string();
+            }
+        }
+    }
+    let dev_dir = format!("{}/device", disk_path);
+    let queue_dir = format!("{}/queue", disk_path);
+
+    let mut ret: BlkAttributes = Default::default();
+    // Sysfs always use 512 bytes sector for size.
+    ret.size = read_attr_u64(&format!("{}/size", sysfs_path)) * 512;
+    ret.vendor = read_attr(&format!("{}/vendor", dev_dir));
+    // MMC use 'name' for product name.
+    ret.model = read_attr_any(&dev_dir, &["model", "name"]);
+    // SCSI use 'rev', NVMe use 'firmware_rev' and MMC use 'fwrev'.
+    ret.revision = read_attr_any(&dev_dir, &["rev", "firmware_rev", "fwrev"]);
+    // virtio-blk place serial in block folder.
+    ret.serial = read_attr(&format!("{}/serial", dev_dir));
+    if ret.serial.is_empty() {
+        ret.serial = read_attr(&format!("{}/serial", disk_path));
+    }
+    if ret.serial.is_empty() {
+        ret.serial = scsi_vpd_serial(&dev_dir);
+    }
+    ret.rotational = read_attr(&format!("{}/rotational", queue_dir)) == "1";
+    ret.logical_block_size =
+        read_attr_u64(&format!("{}/logical_block_size", queue_dir));
+    ret.physical_block_size =
+        read_attr_u64(&format!("{}/physical_block_size", queue_dir));
+    // Kernel older than 4.10 does not support zoned block device.
+    ret.zoned = read_attr(&format!("{}/zoned", queue_dir));
+    if ret.zoned.is_empty() {
+        ret.zoned = "none".to_string();
+    }
+    Ok(ret)
+}
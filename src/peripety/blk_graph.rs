@@ -0,0 +1,175 @@
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use serde_json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BlkGraphNode {
+    pub blk_name: String, // kernel name of block, like 'sda' or 'dm-0'
+    pub blk_path: String,
+    pub major_minor: String,
+    pub holders: Vec<String>, // blocks built on top of this block
+    pub slaves: Vec<String>,  // blocks this block is built on
+}
+
+// Directed graph of all blocks in system. Unlike `BlkInfo.owners`, blocks
+// used by several holders and holders using several slaves are all included.
+// Partition is treated as holder of its disk.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BlkGraph {
+    pub nodes: BTreeMap<String, BlkGraphNode>,
+}
+
+fn read_dir_names(path: &str) -> Vec<String> {
+    let mut ret: Vec<String> = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    ret.sort();
+    ret
+}
+
+// Sysfs folder of partition is placed under the folder of its disk.
+fn disk_of_partition(sysfs_path: &str) -> Option<String> {
+    if !Path::new(&format!("{}/partition", sysfs_path)).exists() {
+        return None;
+    }
+    Path::new(sysfs_path)
+        .canonicalize()
+        .ok()?
+        .parent()?
+        .file_name()?
+        .to_str()
+        .map(|s| s.to_string())
+}
+
+pub(crate) fn blk_path_of(blk_name: &str) -> String {
+    match Sysfs::read(&format!("/sys/class/block/{}/dm/name", blk_name)) {
+        Ok(n) => format!("/dev/mapper/{}", n),
+        Err(_) => format!("/dev/{}", blk_name),
+    }
+}
+
+// Blocks directly built on top of specified block, like multipath device or
+// LVM LVs using a disk. Partitions are not included.
+pub(crate) fn holders_of(blk_name: &str) -> Vec<String> {
+    read_dir_names(&format!("/sys/class/block/{}/holders", blk_name))
+}
+
+impl BlkGraph {
+    pub fn new() -> Result<BlkGraph, PeripetyError> {
+        let mut ret: BlkGraph = Default::default();
+        let mut parts = Vec::new();
+        if let Err(e) = fs::read_dir("/sys/class/block") {
+            return Err(PeripetyError::InternalBug(format!(
+                "Failed to read dir /sys/class/block: {}",
+                e
+            )));
+        }
+        for blk_name in read_dir_names("/sys/class/block") {
+            let sysfs_path = format!("/sys/class/block/{}", blk_name);
+            let mut node: BlkGraphNode = Default::default();
+            // Block might be removed during the scan.
+            node.major_minor =
+                match Sysfs::read(&format!("{}/dev", sysfs_path)) {
+                    Ok(d) => d,
+                    Err(_) => continue,
+                };
+            node.blk_path = blk_path_of(&blk_name);
+            node.holders = holders_of(&blk_name);
+            node.slaves = read_dir_names(&format!("{}/slaves", sysfs_path));
+            if let Some(disk) = disk_of_partition(&sysfs_path) {
+                node.slaves.push(disk.clone());
+                parts.push((disk, blk_name.clone()));
+            }
+            node.blk_name = blk_name.clone();
+            ret.nodes.insert(blk_name, node);
+        }
+
+        for (disk, part) in parts {
+            if let Some(n) = ret.nodes.get_mut(&disk) {
+                n.holders.push(part);
+                n.holders.sort();
+            }
+        }
+        Ok(ret)
+    }
+
+    // Accept kernel name(sda), major:minor(8:0) or block path(/dev/sda,
+    // /dev/mapper/mpatha).
+    pub fn blk_name_of(&self, blk: &str) -> Option<String> {
+        if self.nodes.contains_key(blk) {
+            return Some(blk.to_string());
+        }
+        if blk.starts_with('/') {
+            let p = Path::new(blk).canonicalize().ok()?;
+            let name = p.file_name()?.to_str()?;
+            if self.nodes.contains_key(name) {
+                return Some(name.to_string());
+            }
+            return None;
+        }
+        self.nodes
+            .values()
+            .find(|n| n.major_minor == blk)
+            .map(|n| n.blk_name.clone())
+    }
+
+    fn walk(&self, blk: &str, get_holder: bool) -> Vec<String> {
+        let mut ret: Vec<String> = Vec::new();
+        let mut pending = match self.blk_name_of(blk) {
+            Some(n) => vec![n],
+            None => return ret,
+        };
+        while let Some(name) = pending.pop() {
+            let node = match self.nodes.get(&name) {
+                Some(n) => n,
+                None => continue,
+            };
+            let next = if get_holder {
+                &node.holders
+            } else {
+                &node.slaves
+            };
+            for n in next {
+                if !ret.contains(n) {
+                    ret.push(n.clone());
+                    pending.push(n.clone());
+                }
+            }
+        }
+        ret
+    }
+
+    // Blocks specified block is built on, recursively. For example, all
+    // multipath paths and SCSI disks of an LVM LV.
+    pub fn ancestors(&self, blk: &str) -> Vec<String> {
+        self.walk(blk, false)
+    }
+
+    // Blocks built on top of specified block, recursively. For example, all
+    // partitions, multipath devices and LVM LVs using a SCSI disk.
+    pub fn descendants(&self, blk: &str) -> Vec<String> {
+        self.walk(blk, true)
+    }
+
+    pub fn to_json_string(&self) -> Result<String, PeripetyError> {
+        match serde_json::to_string(&self) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(PeripetyError::JsonSerializeError(format!("{}", e))),
+        }
+    }
+
+    pub fn to_json_string_pretty(&self) -> Result<String, PeripetyError> {
+        match serde_json::to_string_pretty(&self) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(PeripetyError::JsonSerializeError(format!("{}", e))),
+        }
+    }
+}
@@ -1,11 +1,22 @@
+use super::blk_attr::{self, BlkAttributes};
+use super::blk_graph;
+use super::disk;
 use super::dm;
 use super::error::PeripetyError;
+use super::kdev::KernelDevice;
+use super::loop_dev;
+use super::md;
+use super::nbd;
+use super::nvme;
+use super::partition;
+use super::rbd;
 use super::scsi;
 use super::sysfs::Sysfs;
 
 use libmount::mountinfo;
 use regex::Regex;
 use serde_json;
+use std::collections::HashMap;
 use std::fmt;
 use std::fs;
 use std::io::Read;
@@ -19,6 +30,18 @@ pub enum BlkType {
     Dm,
     DmMultipath,
     DmLvm,
+    Vdo,
+    Rbd,
+    Nbd,
+    PciFunction,
+    ScsiHost,
+    NvmeController,
+    Nvme,
+    MdRaid,
+    VirtioBlk,
+    Xen,
+    Mmc,
+    Loop,
     Partition,
 }
 
@@ -31,6 +54,18 @@ impl fmt::Display for BlkType {
             BlkType::Dm => write!(fmt, "Device Mapper"),
             BlkType::DmMultipath => write!(fmt, "Device Mapper Multipath"),
             BlkType::DmLvm => write!(fmt, "Device Mapper LVM"),
+            BlkType::Vdo => write!(fmt, "Device Mapper VDO"),
+            BlkType::Rbd => write!(fmt, "Ceph RBD"),
+            BlkType::Nbd => write!(fmt, "Network Block Device"),
+            BlkType::PciFunction => write!(fmt, "PCI Function"),
+            BlkType::ScsiHost => write!(fmt, "SCSI Host"),
+            BlkType::NvmeController => write!(fmt, "NVMe Controller"),
+            BlkType::Nvme => write!(fmt, "NVMe"),
+            BlkType::MdRaid => write!(fmt, "MD RAID"),
+            BlkType::VirtioBlk => write!(fmt, "VirtIO Block"),
+            BlkType::Xen => write!(fmt, "Xen Virtual Block"),
+            BlkType::Mmc => write!(fmt, "MMC"),
+            BlkType::Loop => write!(fmt, "Loop"),
             BlkType::Partition => write!(fmt, "Partition"),
         }
     }
@@ -46,6 +81,12 @@ pub struct BlkInfo {
     pub mount_point: Option<String>,
     pub transport_id: String,
     pub owners: Vec<BlkInfo>,
+    // Block type specific information, like RAID level of MD RAID.
+    #[serde(default)]
+    pub extension: HashMap<String, String>,
+    // Only loaded by BlkInfo::load_attributes().
+    #[serde(default)]
+    pub attributes: Option<BlkAttributes>,
 }
 
 impl Default for BlkInfo {
@@ -59,6 +100,8 @@ impl Default for BlkInfo {
             mount_point: None,
             transport_id: String::new(),
             owners: Vec::new(),
+            extension: HashMap::new(),
+            attributes: None,
         }
     }
 }
@@ -94,9 +137,14 @@ impl BlkInfo {
     pub fn list() -> Result<Vec<BlkInfo>, PeripetyError> {
         // Steps:
         //  1. Enumerate /sys/class/block/ folder.
-        //  2. Query dm-[0-9]+
-        //  3. Query sd[a-z]+, if already included by above, skip.
-        //  4. Query nvme[0-9]+n[0-9]+. TODO
+        //  2. Query md[0-9]+, hide its member disks.
+        //  3. Query dm-[0-9]+
+        //  4. Query sd[a-z]+, if already included by above, skip.
+        //  5. Query nvme[0-9]+n[0-9]+, path devices of native NVMe multipath
+        //     are included by their head.
+        //  6. Skip loop devices without backing file attached and nbd
+        //     devices not connected.
+        //  7. Skip blocks not supported yet, like sr0 and zram0.
         // Try dm first as multipath might contain many slaves.
         let dir_entries = match fs::read_dir("/sys/class/block") {
             Ok(d) => d,
@@ -107,14 +155,38 @@ impl BlkInfo {
                 )))
             }
         };
+        let mut md_devs = Vec::new();
         let mut dm_devs = Vec::new();
         let mut other_devs = Vec::new();
+        let nvme_path_reg = Regex::new(r"^nvme[0-9]+c[0-9]+n[0-9]+$")
+            .expect("BUG: Regex string should be valid");
+        let md_reg = Regex::new(r"^md[0-9]+$")
+            .expect("BUG: Regex string should be valid");
+        let loop_reg = Regex::new(r"^loop[0-9]+$")
+            .expect("BUG: Regex string should be valid");
+        let nbd_reg = Regex::new(r"^nbd[0-9]+$")
+            .expect("BUG: Regex string should be valid");
 
         for entry in dir_entries {
             if let Ok(e) = entry {
                 if let Ok(name) = e.file_name().into_string() {
-                    if name.starts_with("dm-") {
+                    if md_reg.is_match(&name) {
+                        md_devs.push(name);
+                    } else if name.starts_with("dm-") {
                         dm_devs.push(name);
+                    } else if nvme_path_reg.is_match(&name)
+                        || (loop_reg.is_match(&name)
+                            && !Path::new(&format!(
+                                "/sys/class/block/{}/loop",
+                                name
+                            )).exists())
+                        || (nbd_reg.is_match(&name)
+                            && !Path::new(&format!(
+                                "/sys/class/block/{}/pid",
+                                name
+                            )).exists())
+                    {
+                        continue;
                     } else {
                         other_devs.push(name);
                     }
@@ -124,10 +196,27 @@ impl BlkInfo {
 
         let mut ret = Vec::new();
 
+        for md_dev in &md_devs {
+            let info = match BlkInfo::new_for_list(&md_dev)? {
+                Some(i) => i,
+                None => continue,
+            };
+            // Member blk_path of dm is '/dev/mapper/<name>', use kernel
+            // names instead.
+            for blk_name in md::member_blk_names(&md_dev) {
+                other_devs.retain(|x| x != &blk_name);
+                dm_devs.retain(|x| x != &blk_name);
+            }
+            ret.push(info);
+        }
+
         for dm_dev in &dm_devs {
-            let info = BlkInfo::new(&dm_dev)?;
+            let info = match BlkInfo::new_for_list(&dm_dev)? {
+                Some(i) => i,
+                None => continue,
+            };
             if info.blk_type == BlkType::DmMultipath {
-                for owner_info in info.owners {
+                for owner_info in &info.owners {
                     let blk_name = match owner_info.blk_path.rfind('/') {
                         Some(i) => &owner_info.blk_path[i + 1..],
                         None => owner_info.blk_path.as_str(),
@@ -135,22 +224,88 @@ impl BlkInfo {
                     other_devs.retain(|x| x != blk_name);
                 }
             }
-            ret.push(BlkInfo::new(&dm_dev)?);
+            ret.push(info);
         }
 
         for other_dev in &other_devs {
-            ret.push(BlkInfo::new(&other_dev)?);
+            if let Some(i) = BlkInfo::new_for_list(&other_dev)? {
+                ret.push(i);
+            }
         }
 
         Ok(ret)
     }
 
+    // Blocks not supported yet(like sr0 or zram0) or removed during the scan
+    // are skipped instead of failing the whole listing.
+    fn new_for_list(blk: &str) -> Result<Option<BlkInfo>, PeripetyError> {
+        match BlkInfo::_new(blk, false) {
+            Ok(i) => Ok(Some(i)),
+            Err(PeripetyError::NoSupport(_))
+            | Err(PeripetyError::BlockNoExists(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Load size, vendor, model and etc from sysfs for this block and all its
+    // owners. Blocks not in /sys/class/block(like SCSI host) are skipped.
+    pub fn load_attributes(&mut self) {
+        let blk_name = Path::new(&self.blk_path)
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_os_string()))
+            .and_then(|n| n.into_string().ok());
+        if let Some(n) = blk_name {
+            self.attributes = blk_attr::blk_attributes_get(&n).ok();
+        }
+        for owner in &mut self.owners {
+            owner.load_attributes();
+        }
+    }
+
+    // Only contains WWID, used when block is already removed from system but
+    // kernel structured log provided its WWID.
+    pub fn new_from_wwid(wwid: &str) -> BlkInfo {
+        let mut ret: BlkInfo = Default::default();
+        ret.wwid = scsi::pretty_wwid(wwid);
+        ret
+    }
+
+    // Return block name(like 'dm-3') of the only failed dm of specified
+    // target type(like "snapshot" or "writecache"). Requires root.
+    pub fn find_failed_dm(target_type: &str) -> Option<String> {
+        dm::find_failed_dm(target_type)
+    }
+
+    // Return block names(like 'dm-3') of VDO volume with specified instance
+    // number used in kernel log.
+    pub fn find_vdo_dms(instance: &str) -> Vec<String> {
+        dm::find_vdo_dms(instance)
+    }
+
+    fn new_pci_function(pci_addr: &str) -> Result<BlkInfo, PeripetyError> {
+        let sysfs_path = format!("/sys/bus/pci/devices/{}", pci_addr);
+        if !Path::new(&sysfs_path).exists() {
+            return Err(PeripetyError::BlockNoExists(format!(
+                "PCI function {} does not exists",
+                pci_addr
+            )));
+        }
+        let mut ret: BlkInfo = Default::default();
+        ret.wwid = format!("pci-{}", pci_addr);
+        ret.blk_type = BlkType::PciFunction;
+        ret.blk_path = sysfs_path;
+        ret.preferred_blk_path = ret.blk_path.clone();
+        ret.transport_id = pci_addr.to_string();
+        Ok(ret)
+    }
+
     // Only check current block without hierarchy information.
     pub fn new_skip_extra(blk: &str) -> Result<BlkInfo, PeripetyError> {
         BlkInfo::_new(blk, true)
     }
 
-    fn _new(
+    pub(crate) fn _new(
         blk: &str,
         skip_holder_check: bool,
     ) -> Result<BlkInfo, PeripetyError> {
@@ -176,16 +331,87 @@ impl BlkInfo {
             }
         }
 
+        // journald _KERNEL_DEVICE: b8:80, +block:sdf, +scsi:6:0:0:0
+        if let Ok(kdev) = blk.parse::<KernelDevice>() {
+            if let Some(s) = kdev.to_blk_str() {
+                return BlkInfo::_new(&s, skip_holder_check);
+            }
+            if let KernelDevice::Other(ref subsystem, ref sysname) = kdev {
+                if subsystem == "scsi" && sysname.starts_with("host") {
+                    return BlkInfo::_new(sysname, skip_holder_check);
+                }
+            }
+            return Err(PeripetyError::NoSupport(format!(
+                "Kernel device '{}' is not a block device",
+                blk
+            )));
+        }
+
+        // Partition: sda1, nvme0n1p2, mmcblk0p1, vda3
+        if partition::is_partition(blk) {
+            return partition::blk_info_get_partition(blk, skip_holder_check);
+        }
+
         // sda
         if blk.starts_with("sd") {
-            // If certain disk is used device-mapper (like multipath or LVM),
-            // return block information for that mpath instead
-            if !skip_holder_check {
-                if let Some(d) = dm::get_holder_dm_name(blk) {
-                    return dm::blk_info_get_dm(&d);
+            // If certain disk is used by single device-mapper or md (like
+            // multipath or RAID), return block information for that holder
+            // instead. Disk used by several holders(like LVM PV holding
+            // several LVs) cannot be represented by any of them, list all
+            // holders in extension instead.
+            let holders = if skip_holder_check {
+                Vec::new()
+            } else {
+                blk_graph::holders_of(blk)
+            };
+            if holders.len() == 1 {
+                if holders[0].starts_with("dm-") {
+                    return dm::blk_info_get_dm(&holders[0]);
+                }
+                if holders[0].starts_with("md") {
+                    return md::blk_info_get_md(&holders[0]);
                 }
             }
-            return scsi::blk_info_get_scsi(blk);
+            let mut ret = scsi::blk_info_get_scsi(blk)?;
+            if holders.len() > 1 {
+                let paths: Vec<String> =
+                    holders.iter().map(|h| blk_graph::blk_path_of(h)).collect();
+                ret.extension.insert("holders".to_string(), paths.join(","));
+            }
+            return Ok(ret);
+        }
+
+        // SCSI host: host4
+        // Not a block, but storage controller holding blocks.
+        if let Ok(reg) = Regex::new(r"^host([0-9]+)$") {
+            if let Some(cap) = reg.captures(blk) {
+                return scsi::blk_info_get_scsi_host(&cap[1]);
+            }
+        }
+
+        // NVMe namespace: nvme0n1, nvme0c1n1
+        if let Ok(reg) = Regex::new(r"^nvme[0-9]+(?:c[0-9]+)?n[0-9]+$") {
+            if reg.is_match(blk) {
+                return nvme::blk_info_get_nvme(blk);
+            }
+        }
+
+        // NVMe controller ID + namespace ID: 0+1, nvme0+1
+        if let Ok(reg) = Regex::new(r"^(?:nvme)?([0-9]+)\+([0-9]+)$") {
+            if let Some(cap) = reg.captures(blk) {
+                return BlkInfo::_new(
+                    &nvme::blk_name_of_ctrl_nsid(&cap[1], &cap[2])?,
+                    skip_holder_check,
+                );
+            }
+        }
+
+        // NVMe controller: nvme0
+        // Not a block, but storage controller holding blocks.
+        if let Ok(reg) = Regex::new(r"^nvme[0-9]+$") {
+            if reg.is_match(blk) {
+                return nvme::blk_info_get_nvme_ctrl(blk);
+            }
         }
 
         // scsi_id: 4:0:1:1
@@ -198,11 +424,52 @@ impl BlkInfo {
             }
         }
 
+        // vda, xvda, mmcblk0
+        if let Ok(reg) = Regex::new(r"^(?:x?vd[a-z]+|mmcblk[0-9]+)$") {
+            if reg.is_match(blk) {
+                return disk::blk_info_get_disk(blk);
+            }
+        }
+
+        // loop0
+        if let Ok(reg) = Regex::new(r"^loop[0-9]+$") {
+            if reg.is_match(blk) {
+                return loop_dev::blk_info_get_loop(blk);
+            }
+        }
+
+        // md127
+        if let Ok(reg) = Regex::new(r"^md[0-9]+$") {
+            if reg.is_match(blk) {
+                return md::blk_info_get_md(blk);
+            }
+        }
+
         // dm-0
         if blk.starts_with("dm-") {
             return dm::blk_info_get_dm(blk);
         }
 
+        // rbd0
+        if blk.starts_with("rbd") {
+            return rbd::blk_info_get_rbd(blk);
+        }
+
+        // nbd0
+        if blk.starts_with("nbd") {
+            return nbd::blk_info_get_nbd(blk);
+        }
+
+        // PCI address: 0000:03:00.0
+        // Not a block, but storage controller holding blocks.
+        if let Ok(reg) =
+            Regex::new(r"^[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7]$")
+        {
+            if reg.is_match(blk) {
+                return BlkInfo::new_pci_function(blk);
+            }
+        }
+
         // major: minor
         if let Ok(reg) = Regex::new(r"^[0-9]+:[0-9]+$") {
             if reg.is_match(blk) {
@@ -213,6 +480,12 @@ impl BlkInfo {
             }
         }
 
+        // device mapper name: vg-lv, mpatha
+        let dm_dev_path = format!("/dev/mapper/{}", blk);
+        if Path::new(&dm_dev_path).exists() {
+            return BlkInfo::_new(&dm_dev_path, skip_holder_check);
+        }
+
         // uuid
         let uuid_dev_path = format!("/dev/disk/by-uuid/{}", blk);
         if Path::new(&uuid_dev_path).exists() {
@@ -333,6 +606,32 @@ impl BlkInfo {
         None
     }
 
+    // Unlike get_mount_point(), this also works after block is removed from
+    // sysfs as /proc/self/mountinfo keeps major:minor(like '8:16') till
+    // file system unmounted.
+    pub fn get_mount_point_of_major_minor(
+        major_minor: &str,
+    ) -> Option<String> {
+        let mut data = Vec::new();
+        fs::File::open("/proc/self/mountinfo")
+            .and_then(|mut f| f.read_to_end(&mut data))
+            .ok()?;
+        for m in mountinfo::Parser::new(&data).filter_map(|e| e.ok()) {
+            if format!("{}:{}", m.major, m.minor) == major_minor {
+                return m.mount_point.to_str().map(|s| s.to_string());
+            }
+        }
+        None
+    }
+
+    // Convert st_dev or st_rdev to "major:minor" string, same as
+    // gnu_dev_major() and gnu_dev_minor() of glibc.
+    pub fn dev_to_major_minor(dev: u64) -> String {
+        let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+        let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+        format!("{}:{}", major, minor)
+    }
+
     pub fn major_minor_to_blk_name(
         major_minor: &str,
     ) -> Result<String, PeripetyError> {
@@ -0,0 +1,88 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::scsi;
+use super::sysfs::Sysfs;
+
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+// Disks not using SCSI: virtio-blk, xen-blkfront and mmcblk.
+
+// Return the first name in /dev/disk/by-id pointing to specified block.
+fn by_id_name(blk_path: &str) -> Option<String> {
+    let dev_folder = "/dev/disk/by-id";
+    let mut names: Vec<String> = fs::read_dir(dev_folder)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|n| {
+            Path::new(dev_folder).join(n).canonicalize().ok()
+                == Some(Path::new(blk_path).to_path_buf())
+        })
+        .collect();
+    names.sort();
+    names.into_iter().next()
+}
+
+fn read_sysfs_id(path: &str) -> Option<String> {
+    if !Path::new(path).exists() {
+        return None;
+    }
+    match Sysfs::read(path) {
+        Ok(ref s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        _ => None,
+    }
+}
+
+// Support query on these formats:
+//  * vda, xvda, mmcblk0
+pub(crate) fn blk_info_get_disk(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    // Skip mmcblk0boot0 and mmcblk0rpmb which share CID with mmcblk0.
+    let disk_reg = Regex::new(r"^(?:x?vd[a-z]+|mmcblk[0-9]+)$")
+        .expect("BUG: Regex string should be valid");
+    if !disk_reg.is_match(blk) {
+        return Err(PeripetyError::NoSupport(format!(
+            "Block {} is not supported yet",
+            blk
+        )));
+    }
+    let (blk_type, serial_path) = if blk.starts_with("vd") {
+        (BlkType::VirtioBlk, format!("/sys/block/{}/serial", blk))
+    } else if blk.starts_with("xvd") {
+        // xen-blkfront does not expose serial.
+        (BlkType::Xen, String::new())
+    } else if blk.starts_with("mmcblk") {
+        // CID is unique for each MMC/SD card.
+        (BlkType::Mmc, format!("/sys/block/{}/device/cid", blk))
+    } else {
+        return Err(PeripetyError::NoSupport(format!(
+            "Block {} is not a virtio, xen or mmc disk",
+            blk
+        )));
+    };
+    if !Path::new(&format!("/sys/block/{}", blk)).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "Block {} does not exists",
+            blk
+        )));
+    }
+
+    let blk_path = format!("/dev/{}", blk);
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = match read_sysfs_id(&serial_path) {
+        Some(s) => scsi::pretty_wwid(&s),
+        None => match by_id_name(&blk_path) {
+            Some(n) => n,
+            // Without serial, the block name is the only thing we got.
+            None => blk.to_string(),
+        },
+    };
+    ret.blk_type = blk_type;
+    ret.preferred_blk_path = match by_id_name(&blk_path) {
+        Some(n) => format!("/dev/disk/by-id/{}", n),
+        None => blk_path.clone(),
+    };
+    ret.blk_path = blk_path;
+    Ok(ret)
+}
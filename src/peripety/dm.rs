@@ -2,23 +2,126 @@ use super::blk_info::{BlkInfo, BlkType};
 use super::error::PeripetyError;
 use super::sysfs::Sysfs;
 
-use std::fs;
+use libc;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
 use std::path::Path;
 
-pub(crate) fn get_holder_dm_name(blk: &str) -> Option<String> {
-    let holders = format!("/sys/block/{}/holders", blk);
-    if let Ok(mut entries) = fs::read_dir(&holders) {
-        if let Some(Ok(holder)) = entries.next() {
-            if let Some(n) = holder.path().file_name() {
-                if let Some(s) = n.to_str() {
-                    if s.starts_with("dm-") {
-                        return Some(s.to_string());
-                    }
-                }
-            }
+static DM_CONTROL_PATH: &'static str = "/dev/mapper/control";
+// Size of struct dm_ioctl and struct dm_target_spec in linux/dm-ioctl.h
+const DM_IOCTL_SIZE: usize = 312;
+const DM_TARGET_SPEC_SIZE: usize = 40;
+const DM_STATUS_TABLE_FLAG: u32 = 1 << 4;
+const DM_BUFFER_SIZE: usize = 16384;
+// _IOWR(DM_IOCTL, DM_TABLE_STATUS_CMD, struct dm_ioctl)
+const DM_TABLE_STATUS: u64 =
+    (3 << 30) | ((DM_IOCTL_SIZE as u64) << 16) | (0xfd << 8) | 12;
+
+fn read_u32(buf: &[u8], offset: usize) -> u32 {
+    let mut b = [0u8; 4];
+    b.copy_from_slice(&buf[offset..offset + 4]);
+    u32::from_ne_bytes(b)
+}
+
+fn write_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+}
+
+// Return (target type, parameters) of each target of specified dm via
+// DM_TABLE_STATUS ioctl which requires root. If `table` is true, the
+// parameters are the table line(like '8:16 2048' of linear target),
+// otherwise the status line(like 'Invalid' of failed snapshot target).
+fn dm_table_status(
+    dm_name: &str,
+    table: bool,
+) -> Result<Vec<(String, String)>, PeripetyError> {
+    // struct dm_ioctl.name is 128 bytes including the tailing \0.
+    if dm_name.len() >= 128 {
+        return Err(PeripetyError::InvalidArgument(format!(
+            "Invalid device mapper name {}",
+            dm_name
+        )));
+    }
+    let fd = match OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(DM_CONTROL_PATH)
+    {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(PeripetyError::InternalBug(format!(
+                "dm::dm_table_status(): Failed to open {}: {}",
+                DM_CONTROL_PATH, e
+            )))
+        }
+    };
+    let mut buf = vec![0u8; DM_BUFFER_SIZE];
+    // Interface version 4.0.0
+    write_u32(&mut buf, 0, 4);
+    write_u32(&mut buf, 12, DM_BUFFER_SIZE as u32);
+    write_u32(&mut buf, 16, DM_IOCTL_SIZE as u32);
+    if table {
+        write_u32(&mut buf, 28, DM_STATUS_TABLE_FLAG);
+    }
+    buf[48..48 + dm_name.len()].copy_from_slice(dm_name.as_bytes());
+    let rc = unsafe {
+        libc::ioctl(fd.as_raw_fd(), DM_TABLE_STATUS as _, buf.as_mut_ptr())
+    };
+    if rc != 0 {
+        return Err(PeripetyError::InternalBug(format!(
+            "dm::dm_table_status(): DM_TABLE_STATUS ioctl failed on {}: {}",
+            dm_name,
+            io::Error::last_os_error()
+        )));
+    }
+
+    let mut ret = Vec::new();
+    let data_start = read_u32(&buf, 16) as usize;
+    let data_end = read_u32(&buf, 12) as usize;
+    let mut offset = data_start;
+    for _ in 0..read_u32(&buf, 20) {
+        if offset + DM_TARGET_SPEC_SIZE > buf.len() {
+            break;
+        }
+        let target_type = c_str_of(&buf[offset + 24..offset + 40]);
+        // Parameters string follows struct dm_target_spec.
+        let params_start = offset + DM_TARGET_SPEC_SIZE;
+        let params = if params_start < data_end.min(buf.len()) {
+            c_str_of(&buf[params_start..data_end.min(buf.len())])
+        } else {
+            String::new()
+        };
+        ret.push((target_type, params));
+        // For DM_TABLE_STATUS, 'next' is the offset from the first target.
+        offset = data_start + read_u32(&buf, offset + 20) as usize;
+    }
+    Ok(ret)
+}
+
+fn c_str_of(buf: &[u8]) -> String {
+    let end = buf.iter().position(|c| *c == 0).unwrap_or_else(|| buf.len());
+    String::from_utf8_lossy(&buf[..end]).to_string()
+}
+
+// Return target types(like 'linear' or 'vdo') of dm table.
+fn dm_target_types(dm_name: &str) -> Result<Vec<String>, PeripetyError> {
+    Ok(dm_table_status(dm_name, true)?
+        .into_iter()
+        .map(|(t, _)| t)
+        .collect())
+}
+
+// Check the 'vdo' target in dm table. Without root, fall back to the dm uuid
+// used by vdo manager and LVM VDO pool.
+fn is_vdo_dm(dm_name: &str, uuid: &str) -> bool {
+    match dm_target_types(dm_name) {
+        Ok(types) => types.iter().any(|t| t == "vdo"),
+        Err(_) => {
+            uuid.starts_with("VDO-")
+                || (uuid.starts_with("LVM-") && uuid.ends_with("-vpool"))
         }
     }
-    None
 }
 
 // Support query on these formats:
@@ -33,12 +136,42 @@ pub(crate) fn blk_info_get_dm(blk: &str) -> Result<BlkInfo, PeripetyError> {
         ret.wwid = Sysfs::read(&sysfs_uuid)?;
         ret.preferred_blk_path = format!("/dev/mapper/{}", &name);
         ret.blk_path = ret.preferred_blk_path.clone();
-        if ret.wwid.starts_with("LVM-") {
+        if is_vdo_dm(&name, &ret.wwid) {
+            ret.blk_type = BlkType::Vdo;
+        } else if ret.wwid.starts_with("LVM-") {
             ret.blk_type = BlkType::DmLvm;
         } else if ret.wwid.starts_with("mpath-") {
             ret.blk_type = BlkType::DmMultipath;
         } else if ret.wwid.starts_with("part") {
+            // kpartx partition uses dm uuid 'part<N>-<uuid of disk>'.
+            // Its start sector is only stored in dm table, not in sysfs.
             ret.blk_type = BlkType::Partition;
+            if let Some(i) = ret.wwid.find('-') {
+                let part = ret.wwid["part".len()..i].to_string();
+                ret.extension.insert("partition_number".to_string(), part);
+            }
+            // kpartx creates single linear target: '<major:minor> <start>'.
+            // Requires root.
+            if let Ok(targets) = dm_table_status(&name, true) {
+                if targets.len() == 1 && targets[0].0 == "linear" {
+                    if let Some(start) = targets[0].1.split_whitespace().nth(1)
+                    {
+                        ret.extension.insert(
+                            "partition_start_sector".to_string(),
+                            start.to_string(),
+                        );
+                    }
+                }
+            }
+            let sysfs_size = format!("/sys/block/{}/size", &blk);
+            if let Ok(Ok(s)) =
+                Sysfs::read(&sysfs_size).map(|s| s.parse::<u64>())
+            {
+                ret.extension.insert(
+                    "partition_size".to_string(),
+                    (s * 512).to_string(),
+                );
+            }
         } else {
             ret.blk_type = BlkType::Dm;
         }
@@ -80,3 +213,104 @@ pub(crate) fn blk_info_get_dm(blk: &str) -> Result<BlkInfo, PeripetyError> {
         blk, sysfs_uuid
     )))
 }
+
+// Some device mapper targets(like snapshot and writecache) do not include
+// device in kernel error log. Return block name(like 'dm-3') of the only dm
+// with specified target type whose status line indicates failure. Return
+// None if no such dm or more than one, so we never point to the wrong
+// device.
+pub(crate) fn find_failed_dm(target_type: &str) -> Option<String> {
+    let mut ret = Vec::new();
+    for entry in fs::read_dir("/sys/block").ok()? {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        if !name.starts_with("dm-") {
+            continue;
+        }
+        let sysfs_name = format!("/sys/block/{}/dm/name", &name);
+        let dm_name = match Sysfs::read(&sysfs_name) {
+            Ok(n) => n,
+            Err(_) => continue,
+        };
+        let status = match dm_table_status(&dm_name, false) {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        if status
+            .iter()
+            .any(|&(ref t, ref s)| t == target_type && is_failed(t, s))
+        {
+            ret.push(name);
+        }
+    }
+    if ret.len() == 1 {
+        ret.pop()
+    } else {
+        None
+    }
+}
+
+// Snapshot status is 'Invalid' or 'Overflow' when failed.
+// Writecache status starts with error number, 0 means no error.
+fn is_failed(target_type: &str, status: &str) -> bool {
+    match target_type {
+        "snapshot" => status == "Invalid" || status == "Overflow",
+        "writecache" => match status.split_whitespace().next() {
+            Some(e) => e != "0",
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// kvdo does not include device mapper name in kernel log but instance
+// number which is also exposed as /sys/kvdo/<dm name>/instance. Return
+// block name(like 'dm-3') of that VDO volume. The in-kernel dm-vdo has no
+// such sysfs file, return all VDO volumes then.
+pub(crate) fn find_vdo_dms(instance: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    if let Ok(entries) = fs::read_dir("/sys/kvdo") {
+        for entry in entries {
+            let name = match entry.map(|e| e.file_name().into_string()) {
+                Ok(Ok(n)) => n,
+                _ => continue,
+            };
+            let sysfs_instance = format!("/sys/kvdo/{}/instance", &name);
+            match Sysfs::read(&sysfs_instance) {
+                Ok(ref i) if i == instance => (),
+                _ => continue,
+            }
+            let dev_path = format!("/dev/mapper/{}", &name);
+            if let Ok(p) = Path::new(&dev_path).canonicalize() {
+                if let Some(n) = p.file_name().and_then(|n| n.to_str()) {
+                    ret.push(n.to_string());
+                }
+            }
+        }
+        return ret;
+    }
+    let entries = match fs::read_dir("/sys/block") {
+        Ok(e) => e,
+        Err(_) => return ret,
+    };
+    for entry in entries {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        if !name.starts_with("dm-") {
+            continue;
+        }
+        let dm_name = Sysfs::read(&format!("/sys/block/{}/dm/name", &name));
+        let uuid = Sysfs::read(&format!("/sys/block/{}/dm/uuid", &name));
+        if let (Ok(dm_name), Ok(uuid)) = (dm_name, uuid) {
+            if is_vdo_dm(&dm_name, &uuid) {
+                ret.push(name);
+            }
+        }
+    }
+    ret.sort();
+    ret
+}
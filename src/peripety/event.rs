@@ -80,12 +80,26 @@ pub enum StorageSubSystem {
     Other,
     Scsi,
     DmDirtyLog,
+    DmSnapshot,
+    DmCache,
+    DmWriteCache,
+    DmThin,
+    Vdo,
     LvmThin,
+    Lvm,
     Multipath,
     FsExt4,
     FsJbd2, // The generic journaling layer for block used by ext4 and ocfs2.
     FsXfs,
+    FsGfs2,
+    FsOcfs2,
     Nvme,
+    Rbd,
+    Nbd,
+    Smart,
+    Swap,
+    Pcie,
+    Block, // Block device added, removed or changed.
     Peripety, // For event generated by peripetyd itself.
 }
 
@@ -95,12 +109,26 @@ impl FromStr for StorageSubSystem {
         match s.to_uppercase().as_ref() {
             "SCSI" => Ok(StorageSubSystem::Scsi),
             "DM-DIRTYLOG" => Ok(StorageSubSystem::DmDirtyLog),
+            "DM-SNAPSHOT" => Ok(StorageSubSystem::DmSnapshot),
+            "DM-CACHE" => Ok(StorageSubSystem::DmCache),
+            "DM-WRITECACHE" => Ok(StorageSubSystem::DmWriteCache),
+            "DM-THIN" => Ok(StorageSubSystem::DmThin),
+            "VDO" => Ok(StorageSubSystem::Vdo),
             "LVM-THINPROVISIONING" => Ok(StorageSubSystem::LvmThin),
+            "LVM" => Ok(StorageSubSystem::Lvm),
             "MULTIPATH" => Ok(StorageSubSystem::Multipath),
             "EXT4" => Ok(StorageSubSystem::FsExt4),
             "XFS" => Ok(StorageSubSystem::FsXfs),
+            "GFS2" => Ok(StorageSubSystem::FsGfs2),
+            "OCFS2" => Ok(StorageSubSystem::FsOcfs2),
             "NVME" => Ok(StorageSubSystem::Nvme),
+            "RBD" => Ok(StorageSubSystem::Rbd),
+            "NBD" => Ok(StorageSubSystem::Nbd),
             "JBD2" => Ok(StorageSubSystem::FsJbd2),
+            "SMART" => Ok(StorageSubSystem::Smart),
+            "SWAP" => Ok(StorageSubSystem::Swap),
+            "PCIE" => Ok(StorageSubSystem::Pcie),
+            "BLOCK" => Ok(StorageSubSystem::Block),
             "PERIPETY" => Ok(StorageSubSystem::Peripety),
             _ => Err(PeripetyError::StorageSubSystemParseError(format!(
                 "Invalid StorageSubSystem string {}",
@@ -117,12 +145,26 @@ impl fmt::Display for StorageSubSystem {
             StorageSubSystem::Other => write!(fmt, "Other"),
             StorageSubSystem::Scsi => write!(fmt, "SCSI"),
             StorageSubSystem::DmDirtyLog => write!(fmt, "DM-DirtyLog"),
+            StorageSubSystem::DmSnapshot => write!(fmt, "DM-Snapshot"),
+            StorageSubSystem::DmCache => write!(fmt, "DM-Cache"),
+            StorageSubSystem::DmWriteCache => write!(fmt, "DM-WriteCache"),
+            StorageSubSystem::DmThin => write!(fmt, "DM-Thin"),
+            StorageSubSystem::Vdo => write!(fmt, "VDO"),
             StorageSubSystem::LvmThin => write!(fmt, "LVM-ThinProvisioning"),
+            StorageSubSystem::Lvm => write!(fmt, "LVM"),
             StorageSubSystem::Multipath => write!(fmt, "Multipath"),
             StorageSubSystem::FsExt4 => write!(fmt, "ext4"),
             StorageSubSystem::FsJbd2 => write!(fmt, "jbd2"),
             StorageSubSystem::FsXfs => write!(fmt, "xfs"),
+            StorageSubSystem::FsGfs2 => write!(fmt, "gfs2"),
+            StorageSubSystem::FsOcfs2 => write!(fmt, "ocfs2"),
             StorageSubSystem::Nvme => write!(fmt, "NVMe"),
+            StorageSubSystem::Rbd => write!(fmt, "RBD"),
+            StorageSubSystem::Nbd => write!(fmt, "NBD"),
+            StorageSubSystem::Smart => write!(fmt, "SMART"),
+            StorageSubSystem::Swap => write!(fmt, "Swap"),
+            StorageSubSystem::Pcie => write!(fmt, "PCIe"),
+            StorageSubSystem::Block => write!(fmt, "Block"),
             StorageSubSystem::Peripety => write!(fmt, "Peripety"),
         }
     }
@@ -143,6 +185,10 @@ pub struct StorageEvent {
     pub msg: String,
     pub raw_msg: String,
     pub extension: HashMap<String, String>,
+    // Block information is resolved long after the log was generated, for
+    // example logs generated before daemon started.
+    #[serde(default)]
+    pub blk_info_resolved_late: bool,
 }
 
 impl Default for StorageEvent {
@@ -160,6 +206,7 @@ impl Default for StorageEvent {
             msg: String::new(),
             raw_msg: String::new(),
             extension: HashMap::new(),
+            blk_info_resolved_late: false,
         }
     }
 }
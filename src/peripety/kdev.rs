@@ -0,0 +1,104 @@
+use super::error::PeripetyError;
+
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+// The `_KERNEL_DEVICE` field of journald, documented in
+// systemd.journal-fields(7):
+//  * b8:80             -- block device major:minor
+//  * c189:1            -- character device major:minor
+//  * n2                -- network interface index
+//  * +scsi:6:0:0:0     -- +subsystem:sysname for other devices
+//  * +block:sdf
+#[derive(Clone, PartialEq, Debug)]
+pub enum KernelDevice {
+    Block(String), // major:minor
+    Char(String), // major:minor
+    Net(String), // interface index
+    Other(String, String), // subsystem, sysname
+}
+
+impl FromStr for KernelDevice {
+    type Err = PeripetyError;
+    fn from_str(s: &str) -> Result<KernelDevice, PeripetyError> {
+        if s.starts_with('+') {
+            if let Some(index) = s.find(':') {
+                let subsystem = &s[1..index];
+                let sysname = &s[index + 1..];
+                if !subsystem.is_empty() && !sysname.is_empty() {
+                    return Ok(KernelDevice::Other(
+                        subsystem.to_string(),
+                        sysname.to_string(),
+                    ));
+                }
+            }
+        } else {
+            let reg = Regex::new(r"^([bcn])([0-9]+(?::[0-9]+)?)$")
+                .expect("BUG: KernelDevice::from_str()");
+            // ^ We never panic as above regex string is valid.
+            if let Some(cap) = reg.captures(s) {
+                let value = cap[2].to_string();
+                let is_major_minor = value.contains(':');
+                match &cap[1] {
+                    "b" if is_major_minor => {
+                        return Ok(KernelDevice::Block(value))
+                    }
+                    "c" if is_major_minor => {
+                        return Ok(KernelDevice::Char(value))
+                    }
+                    "n" if !is_major_minor => {
+                        return Ok(KernelDevice::Net(value))
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Err(PeripetyError::InvalidArgument(format!(
+            "Invalid kernel device string {}",
+            s
+        )))
+    }
+}
+
+impl fmt::Display for KernelDevice {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KernelDevice::Block(ref m) => write!(fmt, "b{}", m),
+            KernelDevice::Char(ref m) => write!(fmt, "c{}", m),
+            KernelDevice::Net(ref i) => write!(fmt, "n{}", i),
+            KernelDevice::Other(ref subsystem, ref sysname) => {
+                write!(fmt, "+{}:{}", subsystem, sysname)
+            }
+        }
+    }
+}
+
+impl KernelDevice {
+    // Return block string supported by BlkInfo, or None if this kernel device
+    // is not a block device(like SCSI host or network interface).
+    pub fn to_blk_str(&self) -> Option<String> {
+        match *self {
+            KernelDevice::Block(ref m) => Some(m.to_string()),
+            KernelDevice::Other(ref subsystem, ref sysname) => {
+                match subsystem.as_ref() {
+                    "block" => Some(sysname.to_string()),
+                    "scsi" => {
+                        // SCSI host and target are also using 'scsi'
+                        // subsystem, only scsi_id(h:c:t:l) is for disk.
+                        let reg = Regex::new(r"^(?:[0-9]+:){3}[0-9]+$")
+                            .expect("BUG: KernelDevice::to_blk_str()");
+                        // ^ We never panic as above regex string is valid.
+                        if reg.is_match(sysname) {
+                            Some(sysname.to_string())
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
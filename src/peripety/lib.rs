@@ -36,6 +36,8 @@ extern crate regex;
 extern crate sdjournal;
 extern crate serde_json;
 
+pub use self::blk_attr::BlkAttributes;
+pub use self::blk_graph::{BlkGraph, BlkGraphNode};
 pub use self::blk_info::{BlkInfo, BlkType};
 pub use self::error::PeripetyError;
 pub use self::event::{
@@ -43,11 +45,22 @@ pub use self::event::{
     StorageSubSystem,
 };
 pub use self::filter::{StorageEventFilter, StorageEventFilterType};
+pub use self::kdev::KernelDevice;
 
+mod blk_attr;
+mod blk_graph;
 mod blk_info;
+mod disk;
 mod dm;
 mod error;
 mod event;
 mod filter;
+mod kdev;
+mod loop_dev;
+mod md;
+mod nbd;
+mod nvme;
+mod partition;
+mod rbd;
 mod scsi;
 mod sysfs;
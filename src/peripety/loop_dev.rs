@@ -0,0 +1,47 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+// Support query on these formats:
+//  * loop0
+pub(crate) fn blk_info_get_loop(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    let loop_dir = format!("/sys/block/{}/loop", blk);
+    // Kernel only creates this folder when loop device is attached.
+    if !Path::new(&loop_dir).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "Loop device {} is not attached",
+            blk
+        )));
+    }
+    let backing_file = Sysfs::read(&format!("{}/backing_file", loop_dir))?;
+    let offset = Sysfs::read(&format!("{}/offset", loop_dir))?;
+    let read_only = Sysfs::read(&format!("/sys/block/{}/ro", blk))? == "1";
+
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = format!("loop-{}-{}", backing_file, offset);
+    ret.blk_type = BlkType::Loop;
+    ret.blk_path = format!("/dev/{}", blk);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    ret.transport_id = backing_file.clone();
+    ret.extension
+        .insert("loop_backing_file".to_string(), backing_file.clone());
+    ret.extension.insert("loop_offset".to_string(), offset);
+    ret.extension
+        .insert("loop_read_only".to_string(), read_only.to_string());
+
+    // The block holding backing file. Backing file might be removed or on
+    // file system without block device(like tmpfs), which has major 0.
+    if let Ok(m) = fs::metadata(&backing_file) {
+        let major_minor = BlkInfo::dev_to_major_minor(m.dev());
+        if !major_minor.starts_with("0:") {
+            if let Ok(owner) = BlkInfo::new_skip_extra(&major_minor) {
+                ret.owners.push(owner);
+            }
+        }
+    }
+    Ok(ret)
+}
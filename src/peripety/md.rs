@@ -0,0 +1,106 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use std::fs;
+use std::path::Path;
+
+// Older kernel does not have md/uuid in sysfs, fallback to udev link
+// /dev/disk/by-id/md-uuid-<uuid>.
+fn md_uuid(blk: &str) -> Result<String, PeripetyError> {
+    let sysfs_uuid = format!("/sys/block/{}/md/uuid", blk);
+    if Path::new(&sysfs_uuid).exists() {
+        return Sysfs::read(&sysfs_uuid);
+    }
+    let dev_folder = "/dev/disk/by-id";
+    let blk_path = Path::new("/dev").join(blk);
+    if let Ok(entries) = fs::read_dir(dev_folder) {
+        for entry in entries {
+            let name = match entry.map(|e| e.file_name().into_string()) {
+                Ok(Ok(n)) => n,
+                _ => continue,
+            };
+            if !name.starts_with("md-uuid-") {
+                continue;
+            }
+            if let Ok(p) = Path::new(dev_folder).join(&name).canonicalize() {
+                if p == blk_path {
+                    return Ok(name["md-uuid-".len()..].to_string());
+                }
+            }
+        }
+    }
+    Err(PeripetyError::InternalBug(format!(
+        "md::md_uuid(): No array UUID found for {}",
+        blk
+    )))
+}
+
+fn read_md_attr(blk: &str, name: &str) -> Option<String> {
+    let path = format!("/sys/block/{}/md/{}", blk, name);
+    if !Path::new(&path).exists() {
+        return None;
+    }
+    Sysfs::read(&path).ok()
+}
+
+// Return kernel names(like 'sda' or 'dm-3') of MD RAID members. Member
+// folder in sysfs is named as 'dev-<kernel name>'.
+pub(crate) fn member_blk_names(blk: &str) -> Vec<String> {
+    let md_dir = format!("/sys/block/{}/md", blk);
+    let mut ret: Vec<String> = match fs::read_dir(&md_dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|n| n.starts_with("dev-"))
+            .map(|n| n["dev-".len()..].to_string())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    ret.sort();
+    ret
+}
+
+// Support query on these formats:
+//  * md127
+pub(crate) fn blk_info_get_md(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    let md_dir = format!("/sys/block/{}/md", blk);
+    if !Path::new(&md_dir).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "MD RAID {} does not exists",
+            blk
+        )));
+    }
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = md_uuid(blk)?;
+    ret.blk_type = BlkType::MdRaid;
+    ret.blk_path = format!("/dev/{}", blk);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    // 'degraded' and 'sync_action' only exist for RAID level with redundancy.
+    for name in &["level", "array_state", "degraded", "sync_action"] {
+        if let Some(v) = read_md_attr(blk, name) {
+            ret.extension.insert(format!("md_{}", name), v);
+        }
+    }
+
+    if let Err(e) = fs::read_dir(&md_dir) {
+        return Err(PeripetyError::InternalBug(format!(
+            "md::blk_info_get_md(): Failed to read_dir {}: {}",
+            md_dir, e
+        )));
+    }
+    for member_blk in member_blk_names(blk) {
+        if let Ok(mut member_info) = BlkInfo::new_skip_extra(&member_blk) {
+            for name in &["state", "slot"] {
+                let attr = format!("dev-{}/{}", member_blk, name);
+                if let Some(v) = read_md_attr(blk, &attr) {
+                    member_info
+                        .extension
+                        .insert(format!("md_member_{}", name), v);
+                }
+            }
+            ret.owners.push(member_info);
+        }
+    }
+    Ok(ret)
+}
@@ -0,0 +1,39 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use std::path::Path;
+
+// Support query on these formats:
+//  * nbd0
+pub(crate) fn blk_info_get_nbd(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    let sysfs_dir = format!("/sys/block/{}", blk);
+    // Kernel creates nbd blocks when module loaded, only connected ones
+    // has the pid of nbd client.
+    if !Path::new(&format!("{}/pid", sysfs_dir)).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "NBD device {} is not connected",
+            blk
+        )));
+    }
+    // The backend identifier is set by nbd client via netlink, commonly
+    // holding server address and export name. Older kernel does not have it.
+    let backend_path = format!("{}/backend", sysfs_dir);
+    let backend = if Path::new(&backend_path).exists() {
+        Sysfs::read(&backend_path)?
+    } else {
+        String::new()
+    };
+
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = if backend.is_empty() {
+        format!("nbd-{}", blk)
+    } else {
+        format!("nbd-{}", backend)
+    };
+    ret.blk_type = BlkType::Nbd;
+    ret.blk_path = format!("/dev/{}", blk);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    ret.transport_id = backend;
+    Ok(ret)
+}
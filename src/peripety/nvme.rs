@@ -0,0 +1,155 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Parse /sys/class/nvme/nvmeX/address like
+// "traddr=192.168.1.1,trsvcid=4420,src_addr=192.168.1.2".
+pub(crate) fn parse_nvme_address(address: &str) -> HashMap<String, String> {
+    let mut ret = HashMap::new();
+    for kv in address.split(',') {
+        let mut kv = kv.splitn(2, '=');
+        if let (Some(k), Some(v)) = (kv.next(), kv.next()) {
+            ret.insert(k.trim().to_string(), v.trim().to_string());
+        }
+    }
+    ret
+}
+
+// Support query on these formats:
+//  * nvme0 (NVMe controller)
+pub(crate) fn blk_info_get_nvme_ctrl(
+    ctrl: &str,
+) -> Result<BlkInfo, PeripetyError> {
+    let sysfs_dir = format!("/sys/class/nvme/{}", ctrl);
+    if !Path::new(&sysfs_dir).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "NVMe controller {} does not exists",
+            ctrl
+        )));
+    }
+    let subsysnqn = Sysfs::read(&format!("{}/subsysnqn", sysfs_dir))?;
+    let cntlid = Sysfs::read(&format!("{}/cntlid", sysfs_dir))?;
+    let transport = Sysfs::read(&format!("{}/transport", sysfs_dir))?;
+    let address = Sysfs::read(&format!("{}/address", sysfs_dir))?;
+    let address = parse_nvme_address(&address);
+
+    let mut ret: BlkInfo = Default::default();
+    // Controller ID is unique within NVMe subsystem.
+    ret.wwid = format!("{}-{}", subsysnqn, cntlid);
+    ret.blk_type = BlkType::NvmeController;
+    ret.blk_path = format!("/dev/{}", ctrl);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    // PCIe controller has no traddr and trsvcid.
+    ret.transport_id = format!(
+        "{},{},{},{}",
+        transport,
+        address.get("traddr").map(|s| s.as_str()).unwrap_or(""),
+        address.get("trsvcid").map(|s| s.as_str()).unwrap_or(""),
+        subsysnqn
+    );
+    Ok(ret)
+}
+
+// Return controller name(like nvme1) of NVMe namespace or path device.
+fn ctrl_of_ns(blk: &str) -> Option<String> {
+    let p = fs::read_link(format!("/sys/block/{}/device", blk)).ok()?;
+    let name = p.file_name()?.to_str()?.to_string();
+    // Native NVMe multipath head is under NVMe subsystem, not controller.
+    if name.starts_with("nvme-subsys") {
+        return None;
+    }
+    Some(name)
+}
+
+fn nvme_wwid(blk: &str) -> Result<String, PeripetyError> {
+    for id in &["wwid", "nguid", "eui", "uuid"] {
+        let path = format!("/sys/block/{}/{}", blk, id);
+        if Path::new(&path).exists() {
+            let wwid = Sysfs::read(&path)?;
+            if !wwid.is_empty() {
+                return Ok(wwid);
+            }
+        }
+    }
+    Err(PeripetyError::InternalBug(format!(
+        "nvme::nvme_wwid(): No wwid found for {}",
+        blk
+    )))
+}
+
+// Support query on these formats:
+//  * nvme0n1 (namespace or native NVMe multipath head)
+//  * nvme0c1n1 (path device of native NVMe multipath)
+pub(crate) fn blk_info_get_nvme(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    if !Path::new(&format!("/sys/block/{}", blk)).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "NVMe namespace {} does not exists",
+            blk
+        )));
+    }
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = nvme_wwid(blk)?;
+    ret.blk_type = BlkType::Nvme;
+    ret.blk_path = format!("/dev/{}", blk);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    if let Some(ctrl) = ctrl_of_ns(blk) {
+        ret.transport_id = blk_info_get_nvme_ctrl(&ctrl)?.transport_id;
+    }
+
+    // Path devices of native NVMe multipath head.
+    let mpath_dir = format!("/sys/block/{}/multipath", blk);
+    if let Ok(entries) = fs::read_dir(&mpath_dir) {
+        let mut paths: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect();
+        paths.sort();
+        for path in paths {
+            ret.owners.push(blk_info_get_nvme(&path)?);
+        }
+    }
+    Ok(ret)
+}
+
+// Support query on these formats:
+//  * 0+1 or nvme0+1 (NVMe controller ID + namespace ID)
+pub(crate) fn blk_name_of_ctrl_nsid(
+    ctrl_id: &str,
+    nsid: &str,
+) -> Result<String, PeripetyError> {
+    let ctrl_dir = format!("/sys/class/nvme/nvme{}", ctrl_id);
+    let entries = match fs::read_dir(&ctrl_dir) {
+        Ok(e) => e,
+        Err(e) => {
+            return Err(PeripetyError::BlockNoExists(format!(
+                "NVMe controller nvme{} does not exists: {}",
+                ctrl_id, e
+            )))
+        }
+    };
+    let reg = Regex::new(r"^nvme[0-9]+(?:c[0-9]+)?n[0-9]+$")
+        .expect("BUG: Regex string should be valid");
+    for entry in entries {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        if !reg.is_match(&name) {
+            continue;
+        }
+        let nsid_path = format!("{}/{}/nsid", ctrl_dir, name);
+        if Path::new(&nsid_path).exists() && Sysfs::read(&nsid_path)? == nsid
+        {
+            return Ok(name);
+        }
+    }
+    Err(PeripetyError::BlockNoExists(format!(
+        "NVMe controller nvme{} has no namespace {}",
+        ctrl_id, nsid
+    )))
+}
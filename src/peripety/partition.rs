@@ -0,0 +1,104 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::scsi;
+use super::sysfs::Sysfs;
+
+use std::path::Path;
+
+// Kernel only create 'partition' file in sysfs folder of partition.
+pub(crate) fn is_partition(blk: &str) -> bool {
+    Path::new(&format!("/sys/class/block/{}/partition", blk)).exists()
+}
+
+// Sysfs folder of partition is placed under the folder of its disk:
+//  /sys/devices/<...>/block/nvme0n1/nvme0n1p2
+fn disk_blk_name_of_part(blk: &str) -> Result<String, PeripetyError> {
+    let sysfs_path = format!("/sys/class/block/{}", blk);
+    let real_path = match Path::new(&sysfs_path).canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            return Err(PeripetyError::InternalBug(format!(
+                "partition::disk_blk_name_of_part(): \
+                 Failed to canonicalize {}: {}",
+                sysfs_path, e
+            )))
+        }
+    };
+    if let Some(p) = real_path.parent() {
+        if let Some(n) = p.file_name() {
+            if let Some(s) = n.to_str() {
+                return Ok(s.to_string());
+            }
+        }
+    }
+    Err(PeripetyError::InternalBug(format!(
+        "partition::disk_blk_name_of_part(): \
+         Failed to find parent of {:?}",
+        real_path
+    )))
+}
+
+// Support query on these formats:
+//  * sda1, sdaa1, nvme0n1p2, mmcblk0p1, vda3, md127p1, loop0p1
+pub(crate) fn blk_info_get_partition(
+    blk: &str,
+    skip_holder_check: bool,
+) -> Result<BlkInfo, PeripetyError> {
+    let sysfs_path = format!("/sys/class/block/{}", blk);
+    if !is_partition(blk) {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "Partition {} does not exists",
+            blk
+        )));
+    }
+    let part = Sysfs::read(&format!("{}/partition", sysfs_path))?;
+    let start = Sysfs::read(&format!("{}/start", sysfs_path))?;
+    // Sysfs always use 512 bytes sector for size.
+    let size =
+        match Sysfs::read(&format!("{}/size", sysfs_path))?.parse::<u64>() {
+            Ok(s) => s * 512,
+            Err(e) => {
+                return Err(PeripetyError::InternalBug(format!(
+                    "partition::blk_info_get_partition(): \
+                 Got invalid size of {}: {}",
+                    blk, e
+                )))
+            }
+        };
+
+    let disk_info =
+        BlkInfo::_new(&disk_blk_name_of_part(blk)?, skip_holder_check)?;
+    let mut blk_path = format!("/dev/{}", &blk);
+    // udev create mpatha-part1 while kpartx create mpatha1
+    if disk_info.blk_type == BlkType::DmMultipath {
+        blk_path = format!("{}-part{}", disk_info.blk_path, part);
+        if !Path::new(&blk_path).exists() {
+            blk_path = format!("{}{}", disk_info.blk_path, part);
+            if !Path::new(&blk_path).exists() {
+                return Err(PeripetyError::BlockNoExists(format!(
+                    "Multipath partition file of {} partition {} \
+                     is missing, please use kpartx to create them",
+                    disk_info.blk_path, part
+                )));
+            }
+        }
+    }
+
+    let uuid = BlkInfo::uuid(&blk_path).ok();
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = format!("{}-part{}", disk_info.wwid, part);
+    ret.blk_type = BlkType::Partition;
+    ret.preferred_blk_path = match uuid {
+        Some(ref u) => format!("/dev/disk/by-uuid/{}", u),
+        None => scsi::get_prefered_blk_path(&blk_path),
+    };
+    ret.blk_path = blk_path;
+    ret.uuid = uuid;
+    ret.extension.insert("partition_number".to_string(), part);
+    ret.extension
+        .insert("partition_start_sector".to_string(), start);
+    ret.extension
+        .insert("partition_size".to_string(), size.to_string());
+    ret.owners = vec![disk_info];
+    Ok(ret)
+}
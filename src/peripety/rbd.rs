@@ -0,0 +1,51 @@
+use super::blk_info::{BlkInfo, BlkType};
+use super::error::PeripetyError;
+use super::sysfs::Sysfs;
+
+use regex::Regex;
+use std::path::Path;
+
+// Support query on these formats:
+//  * rbd0
+pub(crate) fn blk_info_get_rbd(blk: &str) -> Result<BlkInfo, PeripetyError> {
+    let dev_id = match Regex::new(r"^rbd([0-9]+)$")
+        .expect("BUG: Regex string should be valid")
+        .captures(blk)
+    {
+        Some(c) => c
+            .get(1)
+            .expect("BUG: Regex capture group missing")
+            .as_str()
+            .to_string(),
+        None => {
+            return Err(PeripetyError::InternalBug(format!(
+                "rbd::blk_info_get_rbd(): Got invalid rbd blk {}",
+                blk
+            )))
+        }
+    };
+    let sysfs_dir = format!("/sys/bus/rbd/devices/{}", dev_id);
+    if !Path::new(&sysfs_dir).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "RBD device {} does not exists",
+            blk
+        )));
+    }
+    let fsid = Sysfs::read(&format!("{}/cluster_fsid", sysfs_dir))?;
+    let pool = Sysfs::read(&format!("{}/pool", sysfs_dir))?;
+    let image = Sysfs::read(&format!("{}/name", sysfs_dir))?;
+    let image_id = Sysfs::read(&format!("{}/image_id", sysfs_dir))?;
+    let snap = Sysfs::read(&format!("{}/current_snap", sysfs_dir))?;
+
+    let mut ret: BlkInfo = Default::default();
+    // Image ID is unique within pool and never changed by image rename.
+    ret.wwid = format!("rbd-{}-{}-{}", fsid, pool, image_id);
+    if snap != "-" {
+        ret.wwid = format!("{}-{}", ret.wwid, snap);
+    }
+    ret.blk_type = BlkType::Rbd;
+    ret.blk_path = format!("/dev/{}", blk);
+    ret.preferred_blk_path = ret.blk_path.clone();
+    ret.transport_id = format!("{},{},{},{}", fsid, pool, image, snap);
+    Ok(ret)
+}
@@ -56,58 +56,9 @@ pub(crate) fn pretty_wwid(wwid: &str) -> String {
 // Support query on these formats:
 //  * 4:0:0:1
 //  * sda
-//  * sda1
 pub(crate) fn blk_info_get_scsi(blk: &str) -> Result<BlkInfo, PeripetyError> {
     let name;
 
-    // Check if partition
-    if let Ok(reg) = Regex::new("^(sd[a-z]+)([0-9]+)$") {
-        if let Some(cap) = reg.captures(blk) {
-            let name = cap.get(1)
-                .expect("BUG: blk_info_get_scsi()")
-                // ^ We never panic as above regex is valid.
-                .as_str();
-            let part = cap.get(2)
-                .expect("BUG: blk_info_get_scsi()")
-                // ^ We never panic as above regex is valid.
-                .as_str();
-            let blk_info = BlkInfo::new(&name)?;
-            let mut blk_path = format!("/dev/{}", &blk);
-            let uuid = match BlkInfo::uuid(&blk_path) {
-                Ok(u) => Some(u),
-                Err(_) => None,
-            };
-            // udev create mpatha-part1 while kpartx create mpatha1
-            if blk_info.blk_type == BlkType::DmMultipath {
-                blk_path = format!("{}-part{}", blk_info.blk_path, part);
-                if !Path::new(&blk_path).exists() {
-                    blk_path = format!("{}{}", blk_info.blk_path, part);
-                    if !Path::new(&blk_path).exists() {
-                        return Err(PeripetyError::BlockNoExists(format!(
-                            "Multipath partition file of {} partition {} \
-                             is missing, please use kpartx to create them",
-                            blk_info.blk_path, part
-                        )));
-                    }
-                }
-            }
-
-            let preferred_blk_path = if let Some(ref u) = uuid {
-                format!("/dev/disk/by-uuid/{}", u)
-            } else {
-                get_prefered_blk_path(&blk_path)
-            };
-            let mut ret: BlkInfo = Default::default();
-            ret.wwid = format!("{}-part{}", blk_info.wwid, part);
-            ret.blk_type = BlkType::Partition;
-            ret.blk_path = blk_path;
-            ret.preferred_blk_path = preferred_blk_path;
-            ret.uuid = uuid;
-            ret.owners = vec![blk_info];
-            return Ok(ret);
-        }
-    }
-
     // Try 4:0:0:1 format
     let mut sysfs_path = format!("/sys/class/scsi_disk/{}/device/wwid", &blk);
     if Path::new(&sysfs_path).exists() {
@@ -135,7 +86,27 @@ pub(crate) fn blk_info_get_scsi(blk: &str) -> Result<BlkInfo, PeripetyError> {
     )))
 }
 
-fn get_prefered_blk_path(raw_blk_path: &str) -> String {
+// Support query on these formats:
+//  * 4 (SCSI host ID)
+pub(crate) fn blk_info_get_scsi_host(
+    host_id: &str,
+) -> Result<BlkInfo, PeripetyError> {
+    let sysfs_path = format!("/sys/class/scsi_host/host{}", host_id);
+    if !Path::new(&sysfs_path).exists() {
+        return Err(PeripetyError::BlockNoExists(format!(
+            "SCSI host {} does not exists",
+            host_id
+        )));
+    }
+    let mut ret: BlkInfo = Default::default();
+    ret.wwid = format!("scsi-host{}", host_id);
+    ret.blk_type = BlkType::ScsiHost;
+    ret.blk_path = sysfs_path;
+    ret.preferred_blk_path = ret.blk_path.clone();
+    Ok(ret)
+}
+
+pub(crate) fn get_prefered_blk_path(raw_blk_path: &str) -> String {
     let dev_folder = "/dev/disk/by-id";
     let raw_path = Path::new(raw_blk_path);
     let mut matches = Vec::new();
@@ -319,6 +290,83 @@ fn get_fc_info(
     Ok(ret)
 }
 
+// The sysfs path of SAS disk contains the SAS topology, like:
+//  /sys/devices/<...>/host0/port-0:0/expander-0:0/port-0:0:4/
+//      end_device-0:0:4/target0:0:4/0:0:4:0
+// RAID volume exposed by HBA(like mpt3sas IR) has no SAS end device in that
+// path. Missing SAS topology is reported as empty values instead of error.
+fn get_sas_info(scsi_id: &str) -> HashMap<String, String> {
+    let mut ret = HashMap::new();
+    let sysfs_path = format!("/sys/class/scsi_device/{}/device", scsi_id);
+    let dev_path = match Path::new(&sysfs_path).canonicalize() {
+        Ok(p) => p.to_str().unwrap_or("").to_string(),
+        Err(_) => String::new(),
+    };
+    let mut sas_address = String::new();
+    let mut phy_identifier = String::new();
+    if let Some(c) = Regex::new(r"/(end_device-[0-9:]+)/")
+        .expect("BUG: Regex string should be valid")
+        .captures(&dev_path)
+    {
+        let end_dev_dir = format!("/sys/class/sas_device/{}", &c[1]);
+        sas_address = Sysfs::read(&format!("{}/sas_address", end_dev_dir))
+            .unwrap_or_default();
+        phy_identifier =
+            Sysfs::read(&format!("{}/phy_identifier", end_dev_dir))
+                .unwrap_or_default();
+    }
+    ret.insert("sas_address".to_string(), sas_address);
+    ret.insert("phy_identifier".to_string(), phy_identifier);
+
+    // Disk might be directly attached to HBA without expander.
+    // The nearest expander is the one disk is attached to.
+    let mut expander = String::new();
+    let mut expander_sas_address = String::new();
+    if let Some(c) = Regex::new(r"/(expander-[0-9:]+)/")
+        .expect("BUG: Regex string should be valid")
+        .captures_iter(&dev_path)
+        .last()
+    {
+        expander = c[1].to_string();
+        expander_sas_address = Sysfs::read(&format!(
+            "/sys/class/sas_device/{}/sas_address",
+            expander
+        )).unwrap_or_default();
+    }
+    ret.insert("expander".to_string(), expander);
+    ret.insert("expander_sas_address".to_string(), expander_sas_address);
+
+    // SES driver create link 'enclosure_device:<slot>' pointing to
+    // /sys/class/enclosure/<enclosure>/<slot>.
+    let mut enclosure_id = String::new();
+    let mut enclosure_slot = String::new();
+    if let Ok(entries) = fs::read_dir(&sysfs_path) {
+        for entry in entries {
+            let name = match entry.map(|e| e.file_name().into_string()) {
+                Ok(Ok(n)) => n,
+                _ => continue,
+            };
+            if !name.starts_with("enclosure_device:") {
+                continue;
+            }
+            enclosure_slot = name["enclosure_device:".len()..].to_string();
+            let slot_path = format!("{}/{}", sysfs_path, name);
+            if let Ok(p) = Path::new(&slot_path).canonicalize() {
+                if let Some(p) = p.parent().and_then(|p| p.to_str()) {
+                    if let Ok(id) = Sysfs::read(&format!("{}/id", p)) {
+                        enclosure_id = id;
+                    }
+                }
+            }
+            break;
+        }
+    }
+    ret.insert("enclosure_id".to_string(), enclosure_id);
+    ret.insert("enclosure_slot".to_string(), enclosure_slot);
+
+    ret
+}
+
 fn is_iscsi_host(host_id: &str) -> bool {
     Path::new(&format!("/sys/class/iscsi_host/host{}", host_id)).exists()
 }
@@ -327,6 +375,10 @@ fn is_fc_host(host_id: &str) -> bool {
     Path::new(&format!("/sys/class/fc_host/host{}", host_id)).exists()
 }
 
+fn is_sas_host(host_id: &str) -> bool {
+    Path::new(&format!("/sys/class/sas_host/host{}", host_id)).exists()
+}
+
 fn get_scsi_transport_info(
     sd_name: &str,
 ) -> Result<HashMap<String, String>, PeripetyError> {
@@ -358,6 +410,11 @@ fn get_scsi_transport_info(
         for (key, value) in get_fc_info(&host_id, &scsi_id)? {
             ret.insert(key, value);
         }
+    } else if is_sas_host(&host_id) {
+        ret.insert("transport".to_string(), "SAS".to_string());
+        for (key, value) in get_sas_info(&scsi_id) {
+            ret.insert(key, value);
+        }
     }
 
     Ok(ret)
@@ -380,6 +437,16 @@ fn get_transport_id(sd_name: &str) -> Result<String, PeripetyError> {
             } else if t == "FC" {
                 Ok(format!("{},{}", info["host_wwpn"], info["target_wwpn"]))
             // ^ get_fc_info() has already ensured these keys exist.
+            } else if t == "SAS" {
+                Ok(format!(
+                    "{},{},{},{},{}",
+                    info["sas_address"],
+                    info["phy_identifier"],
+                    info["expander_sas_address"],
+                    info["enclosure_id"],
+                    info["enclosure_slot"]
+                ))
+            // ^ get_sas_info() always inserts these keys.
             } else {
                 Ok("".to_string())
             }
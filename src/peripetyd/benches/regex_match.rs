@@ -0,0 +1,99 @@
+// Compare the RegexSet prefilter of RegexConfSet with trying build-in regex
+// confs one by one, which is what collector did before RegexConfSet.
+// Run by: cargo bench -p peripetyd
+
+extern crate peripety;
+extern crate regex;
+
+#[allow(dead_code)]
+#[path = "../buildin_regex.rs"]
+mod buildin_regex;
+#[allow(dead_code)]
+#[path = "../data.rs"]
+mod data;
+
+use buildin_regex::BUILD_IN_REGEX_CONFS;
+use data::{RegexConf, RegexConfSet};
+use std::time::{Duration, Instant};
+
+static CORPUS_SEED: &'static str =
+    include_str!("../../../tests/data/kmsg_corpus.log");
+const CORPUS_ROUNDS: usize = 10000;
+const DISK_NAMES: &[&str] = &["sda", "sdb", "sdc", "sdf", "sdaa", "sdab"];
+
+// Large corpus from recorded kernel logs with different disk names.
+fn corpus() -> Vec<String> {
+    let mut ret = Vec::new();
+    for i in 0..CORPUS_ROUNDS {
+        let disk = DISK_NAMES[i % DISK_NAMES.len()];
+        for line in CORPUS_SEED.lines() {
+            ret.push(line.replace("sdf", disk));
+        }
+    }
+    ret
+}
+
+fn linear_match(confs: &[RegexConf], msg: &str) -> bool {
+    for regex_conf in confs {
+        if regex_conf.syslog_identifier != "kernel" {
+            continue;
+        }
+        if let Some(ref s) = regex_conf.starts_with {
+            if !msg.starts_with(s) {
+                continue;
+            }
+        }
+        if regex_conf.regex.captures(msg).is_some() {
+            return true;
+        }
+    }
+    false
+}
+
+fn regex_set_match(regex_conf_set: &RegexConfSet, msg: &str) -> bool {
+    for regex_conf in regex_conf_set.matches("kernel", msg) {
+        if let Some(ref s) = regex_conf.starts_with {
+            if !msg.starts_with(s) {
+                continue;
+            }
+        }
+        if regex_conf.regex.captures(msg).is_some() {
+            return true;
+        }
+    }
+    false
+}
+
+fn bench<F>(name: &str, msgs: &[String], f: F) -> usize
+where
+    F: Fn(&str) -> bool,
+{
+    let start = Instant::now();
+    let matched = msgs.iter().filter(|m| f(m)).count();
+    let elapsed: Duration = start.elapsed();
+    let nanos = elapsed.as_secs() * 1_000_000_000
+        + u64::from(elapsed.subsec_nanos());
+    println!(
+        "{:<10}: {} messages, {} matched, {} ms, {} ns/message",
+        name,
+        msgs.len(),
+        matched,
+        nanos / 1_000_000,
+        nanos / msgs.len() as u64
+    );
+    matched
+}
+
+fn main() {
+    let confs: Vec<RegexConf> = BUILD_IN_REGEX_CONFS
+        .iter()
+        .flat_map(|c| c.to_regex_confs())
+        .collect();
+    let regex_conf_set = RegexConfSet::new(confs.clone());
+    let msgs = corpus();
+
+    let linear = bench("linear", &msgs, |m| linear_match(&confs, m));
+    let regex_set =
+        bench("regex_set", &msgs, |m| regex_set_match(&regex_conf_set, m));
+    assert_eq!(linear, regex_set, "BUG: RegexConfSet result mismatch");
+}
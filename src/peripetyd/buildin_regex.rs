@@ -2,6 +2,7 @@ use data::RegexConfStr;
 
 pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("device-mapper: multipath:"),
         regex: r"(?x)
                 ^device-mapper:\s
@@ -12,6 +13,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "DM_MPATH_PATH_FAILED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("device-mapper: multipath:"),
         regex: r"(?x)
                 ^device-mapper:\s
@@ -22,6 +24,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "DM_MPATH_PATH_REINSTATED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("device-mapper: dirty region log:"),
         regex: r"(?x)
                 ^device-mapper:\s
@@ -33,6 +36,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "DM_DIRTY_LOG_READ_FAILED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("device-mapper: dirty region log:"),
         regex: r"(?x)
                 ^device-mapper:\s
@@ -44,6 +48,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "DM_DIRTY_LOG_WRITE_FAILED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("sd "),
         regex: r"(?x)
                 ^sd\ \d+:\d+:\d+:\d+:\s
@@ -55,6 +60,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "SCSI_UNALIGNED_PARTIAL_COMPLETION",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("sd "),
         regex: r"(?x)
                 ^sd\ \d+:\d+:\d+:\d+:\s
@@ -65,6 +71,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "SCSI_SPINNING_UP_DISK",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("sd "),
         regex: r"(?x)
                 ^sd\ \d+:\d+:\d+:\d+:\s
@@ -76,6 +83,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "SCSI_SENSE_KEY",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("sd "),
         regex: r"(?x)
                 ^sd\ \d+:\d+:\d+:\d+:\s
@@ -86,6 +94,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "SCSI_ADDITIONAL_SENSE_CODE",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("sd "),
         regex: r"(?x)
                 ^sd\ \d+:\d+:\d+:\d+:\s
@@ -96,6 +105,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "SCSI_MEDIUM_ACCESS_TIMEOUT_OFFLINEING_DISK",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("EXT4-fs "),
         regex: r"(?x)
                 ^EXT4-fs\s
@@ -107,6 +117,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_MOUNTED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("EXT4-fs "),
         regex: r"(?x)
                 ^EXT4-fs\s
@@ -117,6 +128,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_REMOUNT_READ_ONLY",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("EXT4-fs (device "),
         regex: r"(?x)
                 ^EXT4-fs\s
@@ -127,6 +139,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_PANIC",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("EXT4-fs error (device "),
         regex: r"(?x)
                 ^EXT4-fs\ error\s
@@ -136,6 +149,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_ERROR",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("XFS "),
         regex: r"(?x)
                 ^XFS \s
@@ -145,6 +159,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_MOUNTED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("XFS "),
         regex: r"(?x)
                 ^XFS\s
@@ -154,6 +169,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_UNMOUNTED",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("XFS "),
         regex: r"(?x)
                 ^XFS \s
@@ -163,6 +179,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_IO_ERROR",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("EXT4-fs "),
         regex: r"(?x)
                 ^EXT4-fs\s
@@ -174,6 +191,7 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         event_type: "FS_IO_ERROR",
     },
     RegexConfStr {
+        syslog_identifiers: &["kernel"],
         starts_with: Some("JBD2: "),
         regex: r"(?x)
                 ^JBD2:\s
@@ -183,4 +201,461 @@ pub const BUILD_IN_REGEX_CONFS: &[RegexConfStr] = &[
         sub_system: "jbd2",
         event_type: "FS_IO_ERROR",
     },
+    RegexConfStr {
+        syslog_identifiers: &["smartd"],
+        starts_with: Some("Device: "),
+        regex: r"(?x)
+                ^Device:\s
+                (?P<kdev>/dev/[^\s,]+)(?:\ \[[^\]]+\])?,\s
+                SMART\ (?P<attribute_type>Usage|Prefailure)\ Attribute:\s
+                (?P<attribute_id>\d+)\ (?P<name>\S+)\s
+                changed\ from\ (?P<previous_value>\d+)
+                (?:\ \[Raw\ (?P<previous_raw_value>\d+)\])?\ to\s
+                (?P<value>\d+)(?:\ \[Raw\ (?P<raw_value>\d+)\])?
+                ",
+        sub_system: "SMART",
+        event_type: "SMART_ATTRIBUTE_CHANGED",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["smartd"],
+        starts_with: Some("Device: "),
+        regex: r"(?x)
+                ^Device:\s
+                (?P<kdev>/dev/[^\s,]+)(?:\ \[[^\]]+\])?,\s
+                Failed\ SMART\ (?P<attribute_type>usage|Prefailure)?\s?
+                Attribute:\s
+                (?P<attribute_id>\d+)\ (?P<name>[^\s.]+)
+                ",
+        sub_system: "SMART",
+        event_type: "SMART_DISK_FAIL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["smartd"],
+        starts_with: Some("Device: "),
+        regex: r"(?x)
+                ^Device:\s
+                (?P<kdev>/dev/[^\s,]+)(?:\ \[[^\]]+\])?,\s
+                FAILED\ SMART\ self-check
+                ",
+        sub_system: "SMART",
+        event_type: "SMART_DISK_FAIL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["smartd"],
+        starts_with: Some("Device: "),
+        regex: r"(?x)
+                ^Device:\s
+                (?P<kdev>/dev/[^\s,]+)(?:\ \[[^\]]+\])?,\s
+                .*FAILING_NOW
+                ",
+        sub_system: "SMART",
+        event_type: "SMART_DISK_FAIL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["dmeventd", "lvm"],
+        starts_with: Some("WARNING: Thin pool "),
+        regex: r"(?x)
+                ^WARNING:\ Thin\ pool\s
+                (?P<kdev>(?P<pool_name>\S+))\s
+                (?P<usage_type>data|metadata)\ is\ now\s
+                (?P<percent>\d+(?:\.\d+)?)%\ full\.?$
+                ",
+        sub_system: "LVM-ThinProvisioning",
+        event_type: "LVM_THIN_POOL_USAGE",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["dmeventd", "lvm"],
+        starts_with: Some("WARNING: Snapshot "),
+        regex: r"(?x)
+                ^WARNING:\ Snapshot\s
+                (?P<kdev>(?P<snapshot_name>\S+))\ is\ now\s
+                (?P<percent>\d+(?:\.\d+)?)%\ full\.?$
+                ",
+        sub_system: "LVM",
+        event_type: "LVM_SNAPSHOT_USAGE",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: snapshots:"),
+        regex: r"(?x)
+                ^device-mapper:\ snapshots:\s
+                Invalidating\ snapshot:\ (?P<reason>.+?)\.?$
+                ",
+        sub_system: "DM-Snapshot",
+        event_type: "DM_SNAPSHOT_INVALID",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: cache:"),
+        regex: r"(?x)
+                ^device-mapper:\ cache:\s
+                (?P<kdev>\d+:\d+):\ switching\ cache\ to\ fail\ mode
+                ",
+        sub_system: "DM-Cache",
+        event_type: "DM_CACHE_FAIL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: cache:"),
+        regex: r"(?x)
+                ^device-mapper:\ cache:\s
+                (?P<kdev>\d+:\d+):\ switching\ cache\ to\ read-only\ mode
+                ",
+        sub_system: "DM-Cache",
+        event_type: "DM_CACHE_READ_ONLY",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: cache:"),
+        regex: r"(?x)
+                ^device-mapper:\ cache:\s
+                (?P<kdev>\d+:\d+):\ metadata\ operation\s
+                '(?P<operation>[^']+)'\ failed:\ error\ =\ (?P<errno>-?\d+)
+                ",
+        sub_system: "DM-Cache",
+        event_type: "DM_CACHE_METADATA_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: writecache:"),
+        regex: r"(?x)
+                ^device-mapper:\ writecache:\s
+                (?P<reason>(?:I/O\ error\ occurred|
+                error\ (?:flushing|writing)\ metadata|write\ error|
+                kcopyd\ error|hardware\ memory\ error).*)$
+                ",
+        sub_system: "DM-WriteCache",
+        event_type: "DM_WRITECACHE_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["dmeventd", "lvm"],
+        starts_with: Some("Snapshot "),
+        regex: r"(?x)
+                ^Snapshot\ (?P<kdev>\S+)\ changed\ state\ to:\ Invalid
+                ",
+        sub_system: "DM-Snapshot",
+        event_type: "DM_SNAPSHOT_INVALID",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:gfs2|GFS2):\ fsid=(?P<fsid>[^\s:]+:[^\s:]+):\s
+                (?P<reason>withdrawing\ from\ cluster\ at\ user's\ request|
+                withdrawn)
+                ",
+        sub_system: "gfs2",
+        event_type: "FS_WITHDRAWN",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:gfs2|GFS2):\ fsid=(?P<fsid>[^\s:]+:[^\s:]+):\s
+                fatal:\ I/O\ error
+                ",
+        sub_system: "gfs2",
+        event_type: "FS_IO_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("("),
+        regex: r"(?x)
+                ^\((?P<process>
+                (?:o2hb|ocfs2dc|ocfs2cmt)-(?P<uuid>[0-9A-F]+)|[^,]+),
+                \d+,\d+\):(?P<function>(?:ocfs2|o2hb)_\w+):\d+\s
+                ERROR:\ status\ =\ -5$
+                ",
+        sub_system: "ocfs2",
+        event_type: "FS_IO_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("("),
+        regex: r"(?x)
+                ^\((?P<process>[^,]+),\d+,\d+\):o2hb_write_timeout:\d+\s
+                ERROR:\ Heartbeat\ write\ timeout\ to\ device\s
+                (?P<kdev>\S+)\ after\ (?P<timeout_ms>\d+)\ milliseconds$
+                ",
+        sub_system: "ocfs2",
+        event_type: "FS_IO_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("OCFS2: ERROR "),
+        regex: r"(?x)
+                ^OCFS2:\ ERROR\ \(device\ (?P<kdev>[^)]+)\):\s
+                (?P<function>\w+):\ (?P<reason>.+)$
+                ",
+        sub_system: "ocfs2",
+        event_type: "FS_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("OCFS2: File system "),
+        regex: r"(?x)
+                ^OCFS2:\ File\ system\s
+                (?:on\ device\ \((?P<kdev>[^)]+)\)\ )?is\ now\ read-only
+                ",
+        sub_system: "ocfs2",
+        event_type: "FS_READ_ONLY",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:device-mapper:\ )?k?vdo(?P<instance>\d+):
+                (?P<thread>[^:\s]+):\s
+                (?P<reason>.*
+                (?:VDO\ is\ out\ of\ space|Out\ of\ space).*)$
+                ",
+        sub_system: "VDO",
+        event_type: "VDO_OUT_OF_SPACE",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:device-mapper:\ )?k?vdo(?P<instance>\d+):
+                (?P<thread>[^:\s]+):\s
+                (?P<reason>.*
+                (?:Read-only\ mode\ entered|entering\ read-only\ mode).*)$
+                ",
+        sub_system: "VDO",
+        event_type: "VDO_READ_ONLY",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:device-mapper:\ )?k?vdo(?P<instance>\d+):
+                (?P<thread>[^:\s]+):\s
+                (?P<reason>.*
+                (?:[Rr]ecovery\ mode|recovery\ complete|[Rr]eplaying).*)$
+                ",
+        sub_system: "VDO",
+        event_type: "VDO_RECOVERY",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: thin:"),
+        regex: r"(?x)
+                ^device-mapper:\ thin:\ (?P<kdev>\d+:\d+):\s
+                switching\ pool\ to\ (?P<mode>out-of-data-space)\s
+                \((?P<io_mode>queue|error)\ IO\)\ mode
+                ",
+        sub_system: "DM-Thin",
+        event_type: "DM_THIN_POOL_OUT_OF_DATA_SPACE",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: thin:"),
+        regex: r"(?x)
+                ^device-mapper:\ thin:\ (?P<kdev>\d+:\d+):\s
+                switching\ pool\ to\ (?P<mode>read-only|fail)\ mode
+                ",
+        sub_system: "DM-Thin",
+        event_type: "DM_THIN_POOL_DEGRADED",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: thin:"),
+        regex: r"(?x)
+                ^device-mapper:\ thin:\ (?P<kdev>\d+:\d+):\s
+                no\ free\ (?P<usage_type>data|metadata)\ space\ available
+                ",
+        sub_system: "DM-Thin",
+        event_type: "DM_THIN_POOL_FULL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("device-mapper: thin:"),
+        regex: r"(?x)
+                ^device-mapper:\ thin:\ (?P<kdev>\d+:\d+):\s
+                reached\ low\ water\ mark\ for\ data\ device
+                ",
+        sub_system: "DM-Thin",
+        event_type: "DM_THIN_POOL_LOW_WATER_MARK",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("rbd: rbd"),
+        regex: r"(?x)
+                ^rbd:\ (?P<kdev>rbd\d+):\s
+                (?P<reason>.*(?:lost\ watch|watch\ error).*)$
+                ",
+        sub_system: "RBD",
+        event_type: "RBD_WATCH_LOST",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("libceph: osd"),
+        regex: r"(?x)
+                ^libceph:\ (?P<osd>osd\d+)\ down$
+                ",
+        sub_system: "RBD",
+        event_type: "RBD_OSD_DOWN",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:block|nbd):?\ (?P<kdev>nbd\d+):\s
+                (?P<reason>[Cc]onnection\ timed\ out.*)$
+                ",
+        sub_system: "NBD",
+        event_type: "NBD_CONNECTION_TIMEOUT",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?:block|nbd):?\ (?P<kdev>nbd\d+):\s
+                (?P<reason>(?:Receive|Send)\ control\ failed.*|
+                Dead\ connection.*)$
+                ",
+        sub_system: "NBD",
+        event_type: "NBD_CONNECTION_LOST",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^(?P<io_type>Write|Read)-error\ on\ swap-device\s
+                \((?P<kdev>\d+:\d+):(?P<offset>\d+)\)$
+                ",
+        sub_system: "Swap",
+        event_type: "SWAP_IO_ERROR",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^\S+\ (?P<port>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7]):\s
+                (?:AER:\ )?(?:Multiple\ )?
+                Correct(?:ed|able)\s
+                error\ (?:message\ )?received(?:\ from)?:?\s
+                (?P<kdev>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7])
+                ",
+        sub_system: "PCIe",
+        event_type: "PCIE_AER_CORRECTED",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^\S+\ (?P<port>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7]):\s
+                (?:AER:\ )?(?:Multiple\ )?
+                Uncorrect(?:ed|able)\ \(Non-Fatal\)\s
+                error\ (?:message\ )?received(?:\ from)?:?\s
+                (?P<kdev>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7])
+                ",
+        sub_system: "PCIe",
+        event_type: "PCIE_AER_UNCORRECTED_NON_FATAL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: None,
+        regex: r"(?x)
+                ^\S+\ (?P<port>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7]):\s
+                (?:AER:\ )?(?:Multiple\ )?
+                Uncorrect(?:ed|able)\ \(Fatal\)\s
+                error\ (?:message\ )?received(?:\ from)?:?\s
+                (?P<kdev>[0-9a-f]{4}:[0-9a-f]{2}:[0-9a-f]{2}\.[0-7])
+                ",
+        sub_system: "PCIe",
+        event_type: "PCIE_AER_UNCORRECTED_FATAL",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("scsi host"),
+        regex: r"(?x)
+                ^scsi\ (?P<kdev>host\d+):\s
+                (?P<reason>.*(?:[Rr]eset|RESET).*)$
+                ",
+        sub_system: "SCSI",
+        event_type: "SCSI_HOST_RESET",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("scsi host"),
+        regex: r"(?x)
+                ^scsi\ (?P<kdev>host\d+):\s
+                (?P<reason>.*(?:[Ll]ink\ down|LINK\ DOWN|[Ll]oop\ down).*)$
+                ",
+        sub_system: "SCSI",
+        event_type: "SCSI_HOST_LINK_DOWN",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("sd "),
+        regex: r"(?x)
+                ^sd\ (?P<kdev>\d+:\d+:\d+:\d+):\s
+                reservation\ conflict$
+                ",
+        sub_system: "SCSI",
+        event_type: "SCSI_RESERVATION_CONFLICT",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("sd "),
+        regex: r"(?x)
+                ^sd\ (?P<kdev>\d+:\d+:\d+:\d+):\s
+                (?:\[sd[a-z]+\]\ )?(?:tag\#\d+\ )?FAILED\ Result:\s
+                .*(?:RESERVATION_CONFLICT|[Rr]eservation\ conflict)
+                ",
+        sub_system: "SCSI",
+        event_type: "SCSI_RESERVATION_CONFLICT",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("nvme nvme"),
+        regex: r"(?x)
+                ^nvme\ (?P<kdev>nvme\d+):\s
+                starting\ error\ recovery
+                ",
+        sub_system: "nvme",
+        event_type: "NVME_ERROR_RECOVERY",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("nvme nvme"),
+        regex: r"(?x)
+                ^nvme\ (?P<kdev>nvme\d+):\s
+                Reconnecting\ in\ (?P<delay>\d+)\ seconds
+                ",
+        sub_system: "nvme",
+        event_type: "NVME_RECONNECTING",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("nvme nvme"),
+        regex: r"(?x)
+                ^nvme\ (?P<kdev>nvme\d+):\s
+                failed\ to\ connect\ queue:\ (?P<queue>\d+)\s
+                ret=(?P<ret>-?\d+)
+                ",
+        sub_system: "nvme",
+        event_type: "NVME_CONNECT_QUEUE_FAILED",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("nvme nvme"),
+        regex: r"(?x)
+                ^nvme\ (?P<kdev>nvme\d+):\s
+                creating\ (?P<queue_count>\d+)\ I/O\ queues
+                ",
+        sub_system: "nvme",
+        event_type: "NVME_IO_QUEUES_CREATED",
+    },
+    RegexConfStr {
+        syslog_identifiers: &["kernel"],
+        starts_with: Some("nvme nvme"),
+        regex: r"(?x)
+                ^nvme\ (?P<kdev>nvme\d+):\s
+                Successfully\ reconnected
+                ",
+        sub_system: "nvme",
+        event_type: "NVME_RECONNECTED",
+    },
 ];
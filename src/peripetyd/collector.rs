@@ -37,66 +37,202 @@
 use chrono::{Local, SecondsFormat, TimeZone};
 use nix;
 use nix::sys::select::FdSet;
-use peripety::{LogSeverity, StorageEvent, StorageSubSystem, BlkInfo};
+use peripety::{
+    BlkInfo, KernelDevice, LogSeverity, StorageEvent, StorageSubSystem,
+};
+use regex::Captures;
 use sdjournal;
 use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
 use std::os::unix::io::AsRawFd;
+use std::path::Path;
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 use buildin_regex::BUILD_IN_REGEX_CONFS;
 use conf::ConfCollector;
-use data::RegexConf;
+use data::{RegexConf, RegexConfSet};
+
+static JOURNAL_CURSOR_PATH: &'static str = "/var/lib/peripety/journal_cursor";
+static BOOT_ID_PATH: &'static str = "/proc/sys/kernel/random/boot_id";
+pub const DEFAULT_MAX_CATCH_UP_AGE: u64 = 0;
+
+// The structured log patches add extra fields to /dev/kmsg dictionary which
+// might be prefixed with `_KERNEL_` by journald.
+fn get_kmsg_field(
+    entry: &HashMap<String, String>,
+    name: &str,
+) -> Option<String> {
+    entry
+        .get(name)
+        .or_else(|| entry.get(&format!("_KERNEL_{}", name)))
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
+
+// Kernel does not include device in some device mapper, VDO, ceph and
+// cluster file system logs, guess it from device mapper status or file system
+// information. When several devices might be the one, all of them are
+// returned and event will be sent for each of them.
+fn guess_kdevs(sub_system: StorageSubSystem, cap: &Captures) -> Vec<String> {
+    let kdev = match sub_system {
+        StorageSubSystem::DmSnapshot => {
+            BlkInfo::find_failed_dm("snapshot").unwrap_or_default()
+        }
+        StorageSubSystem::DmWriteCache => {
+            BlkInfo::find_failed_dm("writecache").unwrap_or_default()
+        }
+        StorageSubSystem::Vdo => match cap.name("instance") {
+            Some(m) => return BlkInfo::find_vdo_dms(m.as_str()),
+            None => String::new(),
+        },
+        StorageSubSystem::FsGfs2 => match cap.name("fsid") {
+            Some(m) => gfs2_fsid_to_kdev(m.as_str()).unwrap_or_default(),
+            None => String::new(),
+        },
+        StorageSubSystem::FsOcfs2 => match cap.name("uuid") {
+            Some(m) => ocfs2_uuid_to_kdev(m.as_str()).unwrap_or_default(),
+            None => String::new(),
+        },
+        StorageSubSystem::Rbd => return all_rbd_devs(),
+        _ => String::new(),
+    };
+    if kdev.is_empty() {
+        Vec::new()
+    } else {
+        vec![kdev]
+    }
+}
+
+// GFS2 fsid is "<cluster>:<fs_name>.<journal_id>", the sysfs folder is named
+// after lock table "<cluster>:<fs_name>" holding "major:minor" in 'id' file.
+fn gfs2_fsid_to_kdev(fsid: &str) -> Option<String> {
+    let lock_table = match fsid.rfind('.') {
+        Some(i) => &fsid[..i],
+        None => fsid,
+    };
+    let mut contents = String::new();
+    File::open(format!("/sys/fs/gfs2/{}/id", lock_table))
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .ok()?;
+    Some(contents.trim().to_string())
+}
+
+// libceph logs are about the ceph cluster without saying which one, map
+// them to all RBD devices.
+fn all_rbd_devs() -> Vec<String> {
+    let mut ret: Vec<String> = match fs::read_dir("/sys/bus/rbd/devices") {
+        Ok(ids) => ids
+            .filter_map(|i| i.ok())
+            .filter_map(|i| i.file_name().into_string().ok())
+            .map(|i| format!("rbd{}", i))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    ret.sort();
+    ret
+}
+
+// OCFS2 names its threads after file system UUID in upper case without
+// dash, like 'o2hb-1A2B3C4D5E', kernel truncates it to 15 characters.
+// Return the device whose file system UUID starts with that prefix.
+fn ocfs2_uuid_to_kdev(uuid_prefix: &str) -> Option<String> {
+    let mut devs = Vec::new();
+    for entry in fs::read_dir("/dev/disk/by-uuid").ok()? {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        if !name.replace("-", "").to_uppercase().starts_with(uuid_prefix) {
+            continue;
+        }
+        let link = format!("/dev/disk/by-uuid/{}", name);
+        if let Ok(p) = Path::new(&link).canonicalize() {
+            if let Some(p) = p.to_str() {
+                devs.push(p.to_string());
+            }
+        }
+    }
+    // Never point to the wrong device when prefix is not unique.
+    if devs.len() == 1 {
+        devs.pop()
+    } else {
+        None
+    }
+}
 
 fn process_journal_entry(
     entry: &HashMap<String, String>,
     sender: &Sender<StorageEvent>,
-    buildin_regex_confs: &[RegexConf],
-    user_regex_confs: &[RegexConf],
-) {
+    regex_conf_set: &RegexConfSet,
+    start_time: u64,
+) -> bool {
     let msg = match entry.get("MESSAGE") {
         Some(m) => {
             if m.is_empty() {
-                return;
+                return false;
             }
             m
         }
-        None => return,
+        None => return false,
     };
 
-    if !entry.contains_key("SYSLOG_IDENTIFIER") {
-        return;
-    }
+    let syslog_identifier = match entry.get("SYSLOG_IDENTIFIER") {
+        Some(s) => s,
+        None => return false,
+    };
 
     // Skip messages generated by peripetyd.
     if entry.get("IS_PERIPETY") == Some(&"TRUE".to_string()) {
-        return;
+        return false;
     }
 
     // The /dev/kmsg can hold userspace log, hence using `_TRANSPORT=kernel` is
     // not correct here.
-    if entry.get("SYSLOG_IDENTIFIER") != Some(&"kernel".to_string()) {
-        return;
+    let is_kernel = syslog_identifier == "kernel";
+
+    // Save CPU on userspace logs we have no regex for.
+    if !is_kernel && !regex_conf_set.has_syslog_identifier(syslog_identifier)
+    {
+        return false;
     }
 
     let mut event: StorageEvent = Default::default();
+    let mut kmsg_wwid = None;
 
-    // Currently, SCSI layer have limited structured log holding
-    // device and subsystem type, but without regex, we cannot know the
-    // event type. Hence we do regex anyway without checking structured log,
-    // we can do that when kernel provide better structured log.
+    if is_kernel {
+        // Kernel with structured log patches provides device WWID and event
+        // type in /dev/kmsg, use them when available. Regex is still required
+        // for kernel without these patches.
+        kmsg_wwid = get_kmsg_field(entry, "DEVICE_WWID");
+        if let Some(t) = get_kmsg_field(entry, "EVENT_TYPE") {
+            event.event_type = t;
+        }
 
-    if let Some(s) = entry.get("_KERNEL_SUBSYSTEM") {
-        if let Ok(s) = s.parse::<StorageSubSystem>() {
-            event.sub_system = s;
+        if let Some(s) = entry.get("_KERNEL_SUBSYSTEM") {
+            if let Ok(s) = s.parse::<StorageSubSystem>() {
+                event.sub_system = s;
+            }
+        }
+        // Decode structured kernel device, so regex conf could omit the
+        // `kdev` capture group.
+        if let Some(d) = entry.get("_KERNEL_DEVICE") {
+            event.kdev = match d.parse::<KernelDevice>() {
+                Ok(k) => k.to_blk_str().unwrap_or_else(|| d.to_string()),
+                Err(_) => d.to_string(),
+            };
         }
-    }
-    if let Some(d) = entry.get("_KERNEL_DEVICE") {
-        event.kdev = d.to_string();
     }
 
-    for regex_conf in buildin_regex_confs.iter().chain(user_regex_confs.iter())
-    {
+    // Only regex confs matched by the RegexSet need capture extraction.
+    // Regex is used even when kernel provides all structured fields, to
+    // collect extensions from named capture groups.
+    let regex_confs = regex_conf_set.matches(syslog_identifier, msg);
+
+    let mut kdevs = Vec::new();
+    for regex_conf in regex_confs {
         // Save CPU if event.sub_system is defined and not matching with regex
         // config.
         if event.sub_system != StorageSubSystem::Unknown
@@ -111,32 +247,27 @@ fn process_journal_entry(
             }
         }
         if let Some(cap) = regex_conf.regex.captures(msg) {
-            if let Some(m) = cap.name("kdev") {
-                event.kdev = m.as_str().to_string();
-            }
+            // Kernel device provided by kernel takes precedence.
             if event.kdev.is_empty() {
-                continue;
-            }
-            match BlkInfo::new_hierarchy(&event.kdev) {
-                Ok(i) => event.hierarchy_blk_info = i,
-                Err(e) => {
-                    println!("collector: {}", e);
-                    return;
+                if let Some(m) = cap.name("kdev") {
+                    event.kdev = m.as_str().to_string();
                 }
             }
-            match BlkInfo::new_current(&event.kdev) {
-                Ok(i) => event.cur_blk_info = i,
-                Err(e) => {
-                    println!("collector: {}", e);
-                    return;
-                }
+            kdevs = if event.kdev.is_empty() {
+                guess_kdevs(regex_conf.sub_system, &cap)
+            } else {
+                vec![event.kdev.clone()]
+            };
+            if kdevs.is_empty() {
+                continue;
             }
 
-            if regex_conf.sub_system != StorageSubSystem::Unknown {
+            if event.sub_system == StorageSubSystem::Unknown {
                 event.sub_system = regex_conf.sub_system;
             }
 
-            if !regex_conf.event_type.is_empty() {
+            // Event type provided by kernel takes precedence.
+            if event.event_type.is_empty() {
                 event.event_type = regex_conf.event_type.to_string();
             }
 
@@ -158,8 +289,11 @@ fn process_journal_entry(
         }
     }
 
-    if event.sub_system == StorageSubSystem::Unknown || event.kdev.is_empty() {
-        return;
+    if kdevs.is_empty() && !event.kdev.is_empty() {
+        kdevs.push(event.kdev.clone());
+    }
+    if event.sub_system == StorageSubSystem::Unknown || kdevs.is_empty() {
+        return false;
     }
 
     // Add other data
@@ -171,13 +305,15 @@ fn process_journal_entry(
     if let Some(t) = entry.get("__REALTIME_TIMESTAMP") {
         let tp = match t.parse::<i64>() {
             Ok(t) => t,
-            Err(_) => return,
+            Err(_) => return false,
         };
         event.timestamp = Local
             .timestamp(tp / 10i64.pow(6), (tp % 10i64.pow(6)) as u32)
-            .to_rfc3339_opts(SecondsFormat::Micros, false)
+            .to_rfc3339_opts(SecondsFormat::Micros, false);
+        // Log generated before daemon started.
+        event.blk_info_resolved_late = (tp as u64) < start_time;
     } else {
-        return;
+        return false;
     }
 
     if let Some(p) = entry.get("PRIORITY") {
@@ -191,28 +327,86 @@ fn process_journal_entry(
     }
 
     event.raw_msg = msg.to_string();
-    //TODO(Gris Ge): Grouping events by re-using event_id.
+    // Events of the same log share the same event_id.
     event.event_id = Uuid::new_v4().hyphenated().to_string();
 
-    //TODO(Gris Ge): Need to skip journal entry when that one is created by
-    //               peripety.
-    if let Err(e) = sender.send(event) {
-        println!("collector: Failed to send event: {}", e);
+    let mut sent = false;
+    for kdev in kdevs {
+        let mut event = event.clone();
+        event.kdev = kdev;
+        if resolve_blk_info(&mut event, &kmsg_wwid) {
+            //TODO(Gris Ge): Need to skip journal entry when that one is
+            //               created by peripety.
+            match sender.send(event) {
+                Ok(_) => sent = true,
+                Err(e) => println!("collector: Failed to send event: {}", e),
+            }
+        }
     }
+    sent
+}
+
+// Return false if block of event is not found.
+fn resolve_blk_info(
+    event: &mut StorageEvent,
+    kmsg_wwid: &Option<String>,
+) -> bool {
+    // Kernel provided WWID is authoritative. The block name might be removed
+    // and reused by another device before we got this log, so block found by
+    // name with different WWID is ignored.
+    if let Some(ref w) = *kmsg_wwid {
+        let wwid_info = BlkInfo::new_from_wwid(w);
+        match BlkInfo::new_skip_extra(&event.kdev) {
+            Ok(ref i) if i.wwid == wwid_info.wwid => {
+                event.cur_blk_info = i.clone();
+                event.hierarchy_blk_info =
+                    match BlkInfo::new_hierarchy(&event.kdev) {
+                        Ok(h) => h,
+                        Err(_) => wwid_info,
+                    };
+            }
+            _ => {
+                event.cur_blk_info = wwid_info.clone();
+                event.hierarchy_blk_info = wwid_info;
+            }
+        }
+        return true;
+    }
+
+    match BlkInfo::new_hierarchy(&event.kdev) {
+        Ok(i) => event.hierarchy_blk_info = i,
+        Err(e) => {
+            println!("collector: {}", e);
+            return false;
+        }
+    }
+    match BlkInfo::new_skip_extra(&event.kdev) {
+        Ok(i) => event.cur_blk_info = i,
+        Err(e) => {
+            println!("collector: {}", e);
+            return false;
+        }
+    }
+
+    true
 }
 
 pub fn new(
     sender: &Sender<StorageEvent>,
     config_changed: &Receiver<ConfCollector>,
+    max_catch_up_age: u64,
+    backfill_boot: bool,
 ) {
+    // Journal entries before this time are processed after block might be
+    // changed or removed.
+    let start_time = now_usec();
     let mut journal =
         sdjournal::Journal::new().expect("Failed to open systemd journal");
     // We never want to block, so set the timeout to 0
     journal.timeout_us = 0;
-    // Jump to the end as we cannot annotate old journal entries.
-    journal
-        .seek_tail()
-        .expect("Unable to seek to end of journal!");
+    // The entry pointed by saved cursor has already been processed.
+    let mut processed_cursor =
+        seek_journal(&mut journal, max_catch_up_age, backfill_boot);
 
     // Setup initial regex conf.
     let mut buildin_regex_confs: Vec<RegexConf> = Vec::new();
@@ -220,9 +414,9 @@ pub fn new(
 
     // Read config to add more RegexConf.
     for regex_conf_str in BUILD_IN_REGEX_CONFS {
-        let regex_conf = regex_conf_str.to_regex_conf();
-        buildin_regex_confs.push(regex_conf);
+        buildin_regex_confs.extend(regex_conf_str.to_regex_confs());
     }
+    let mut regex_conf_set = RegexConfSet::new(buildin_regex_confs.clone());
 
     loop {
         let mut fds = FdSet::new();
@@ -237,9 +431,17 @@ pub fn new(
             continue;
         }
 
+        // Cursor of last entry not saved yet.
+        let mut pending_cursor = None;
+
         for entry in &mut journal {
             match entry {
                 Ok(entry) => {
+                    if let Some(c) = processed_cursor.take() {
+                        if entry.get("__CURSOR") == Some(&c) {
+                            continue;
+                        }
+                    }
                     if let Ok(conf) = config_changed.try_recv() {
                         user_regex_confs.clear();
                         for regex in conf.regexs {
@@ -254,18 +456,193 @@ pub fn new(
                                 }
                             }
                         }
+                        regex_conf_set = RegexConfSet::new(
+                            buildin_regex_confs
+                                .iter()
+                                .chain(user_regex_confs.iter())
+                                .cloned()
+                                .collect(),
+                        );
                     }
-                    process_journal_entry(
+                    let sent = process_journal_entry(
                         &entry,
                         sender,
-                        &buildin_regex_confs,
-                        &user_regex_confs,
-                    )
+                        &regex_conf_set,
+                        start_time,
+                    );
+                    if max_catch_up_age == 0 && !backfill_boot {
+                        continue;
+                    }
+                    pending_cursor = match (
+                        entry.get("__CURSOR"),
+                        entry.get("__REALTIME_TIMESTAMP"),
+                    ) {
+                        (Some(c), Some(t)) => Some((c.clone(), t.clone())),
+                        _ => pending_cursor,
+                    };
+                    // Save cursor immediately after event sent, so we don't
+                    // generate duplicate event after restart.
+                    if sent {
+                        if let Some((c, t)) = pending_cursor.take() {
+                            save_journal_cursor(&c, &t);
+                        }
+                    }
                 }
                 Err(e) => {
                     println!("Error retrieving the journal entry: {:?}", e)
                 }
             }
         }
+
+        if let Some((c, t)) = pending_cursor {
+            save_journal_cursor(&c, &t);
+        }
+    }
+}
+
+fn now_usec() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => {
+            d.as_secs() * 10u64.pow(6) + u64::from(d.subsec_micros())
+        }
+        Err(_) => 0,
+    }
+}
+
+// Return current boot ID in the format used by journal _BOOT_ID.
+fn current_boot_id() -> Option<String> {
+    match fs::read_to_string(BOOT_ID_PATH) {
+        // The journal _BOOT_ID is using the format without hyphens.
+        Ok(b) => Some(b.trim().replace("-", "")),
+        Err(e) => {
+            println!("collector: Failed to read {}: {}", BOOT_ID_PATH, e);
+            None
+        }
+    }
+}
+
+// Continue from saved journal cursor if it is not older than
+// `max_catch_up_age` seconds or is in current boot when `backfill_boot` is
+// true, else only process new journal entries unless `backfill_boot` is true
+// where we start from the beginning of current boot.
+// When `backfill_boot` is true, journal is limited to current boot as blocks
+// in logs of previous boots might be gone or renamed.
+// Return the saved cursor if journal is seeked to it.
+fn seek_journal(
+    journal: &mut sdjournal::Journal,
+    max_catch_up_age: u64,
+    backfill_boot: bool,
+) -> Option<String> {
+    let mut boot_id = if backfill_boot {
+        current_boot_id()
+    } else {
+        None
+    };
+    if let Some(b) = boot_id.take() {
+        match journal.add_match(&format!("_BOOT_ID={}", b)) {
+            Ok(_) => boot_id = Some(b),
+            Err(e) => println!(
+                "collector: Failed to filter journal by boot ID {}: {:?}",
+                b, e
+            ),
+        }
+    }
+    let mut start = None;
+
+    // Saved cursor is also used to avoid processing current boot again when
+    // `backfill_boot` is true.
+    if max_catch_up_age > 0 || boot_id.is_some() {
+        let oldest =
+            now_usec().saturating_sub(max_catch_up_age * 10u64.pow(6));
+        if let Some((cursor, realtime)) = load_journal_cursor() {
+            // Journal cursor contains boot ID as 'b=<boot_id>' field.
+            let in_cur_boot = match boot_id {
+                Some(ref b) => {
+                    cursor.split(';').any(|f| f == format!("b={}", b))
+                }
+                None => false,
+            };
+            if realtime >= oldest || in_cur_boot {
+                match journal.seek_cursor(&cursor) {
+                    Ok(_) => return Some(cursor),
+                    Err(e) => println!(
+                        "collector: Failed to seek journal cursor {}: {:?}",
+                        cursor, e
+                    ),
+                }
+            } else {
+                start = Some(oldest);
+            }
+        }
+    }
+
+    // Journal is already filtered by boot ID, the head is the first entry
+    // of current boot regardless of wall clock changes during boot.
+    if boot_id.is_some() {
+        match journal.seek_head() {
+            Ok(_) => return None,
+            Err(e) => {
+                println!("collector: Failed to seek journal head: {:?}", e)
+            }
+        }
+    }
+
+    if let Some(s) = start {
+        match journal.seek_realtime_usec(s) {
+            Ok(_) => return None,
+            Err(e) => {
+                println!("collector: Failed to seek journal to {}: {:?}", s, e)
+            }
+        }
+    }
+    // Jump to the end as we cannot annotate old journal entries.
+    journal
+        .seek_tail()
+        .expect("Unable to seek to end of journal!");
+    None
+}
+
+// Return journal cursor and its realtime timestamp in microseconds.
+fn load_journal_cursor() -> Option<(String, u64)> {
+    let mut contents = String::new();
+    match File::open(JOURNAL_CURSOR_PATH) {
+        Ok(mut fd) => {
+            if let Err(e) = fd.read_to_string(&mut contents) {
+                println!(
+                    "collector: Failed to read {}: {}",
+                    JOURNAL_CURSOR_PATH, e
+                );
+                return None;
+            }
+        }
+        Err(_) => return None,
+    };
+    let mut lines = contents.lines();
+    match (lines.next(), lines.next().map(|t| t.parse::<u64>())) {
+        (Some(c), Some(Ok(t))) if !c.is_empty() => Some((c.to_string(), t)),
+        _ => {
+            println!("collector: Invalid content of {}", JOURNAL_CURSOR_PATH);
+            None
+        }
+    }
+}
+
+fn save_journal_cursor(cursor: &str, realtime: &str) {
+    if let Some(dir) = Path::new(JOURNAL_CURSOR_PATH).parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            println!("collector: Failed to create dir {:?}: {}", dir, e);
+            return;
+        }
+    }
+    // Write to temp file then rename, so we never leave a partial cursor.
+    let tmp_path = format!("{}.tmp", JOURNAL_CURSOR_PATH);
+    let rc = File::create(&tmp_path)
+        .and_then(|mut fd| write!(fd, "{}\n{}\n", cursor, realtime))
+        .and_then(|_| fs::rename(&tmp_path, JOURNAL_CURSOR_PATH));
+    if let Err(e) = rc {
+        println!(
+            "collector: Failed to save journal cursor to {}: {}",
+            JOURNAL_CURSOR_PATH, e
+        );
     }
 }
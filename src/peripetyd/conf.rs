@@ -13,10 +13,13 @@ pub struct ConfMain {
     pub notify_stdout: Option<bool>,
     pub save_to_journald: Option<bool>,
     pub dump_blk_info_at_start: Option<bool>,
+    pub max_catch_up_age: Option<u64>,
+    pub backfill_current_boot: Option<bool>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct ConfCollectorRegex {
+    pub syslog_identifier: Option<String>,
     pub regex: String,
     pub event_type: String,
     pub starts_with: Option<String>,
@@ -44,6 +47,10 @@ impl ConfCollectorRegex {
             }
         };
         Ok(RegexConf {
+            syslog_identifier: self
+                .syslog_identifier
+                .clone()
+                .unwrap_or_else(|| "kernel".to_string()),
             starts_with: self.starts_with.clone(),
             regex,
             sub_system,
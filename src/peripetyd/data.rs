@@ -1,5 +1,6 @@
 use peripety::{StorageEvent, StorageSubSystem};
-use regex::Regex;
+use regex::{Regex, RegexSet};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::sync::mpsc::Sender;
@@ -20,14 +21,84 @@ pub struct ParserInfo {
 
 #[derive(Clone, Debug)]
 pub struct RegexConf {
+    pub syslog_identifier: String,
     pub starts_with: Option<String>,
     pub regex: Regex,
     pub sub_system: StorageSubSystem,
     pub event_type: String,
 }
 
+// Regex confs sharing the same syslog identifier compiled into a single
+// RegexSet, so that each journal message is scanned once and only matching
+// confs do capture extraction.
 #[derive(Clone, Debug)]
+struct RegexConfGroup {
+    confs: Vec<RegexConf>,
+    regex_set: Option<RegexSet>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RegexConfSet {
+    groups: HashMap<String, RegexConfGroup>,
+}
+
+impl RegexConfSet {
+    pub fn new(confs: Vec<RegexConf>) -> RegexConfSet {
+        let mut all_confs: HashMap<String, Vec<RegexConf>> = HashMap::new();
+        for conf in confs {
+            all_confs
+                .entry(conf.syslog_identifier.clone())
+                .or_insert_with(Vec::new)
+                .push(conf);
+        }
+        let mut groups = HashMap::new();
+        for (syslog_identifier, confs) in all_confs {
+            let regex_set =
+                match RegexSet::new(confs.iter().map(|c| c.regex.as_str())) {
+                    Ok(s) => Some(s),
+                    Err(e) => {
+                        // Only happens when exceeding the regex size limit,
+                        // fall back to try every regex conf one by one.
+                        println!("collector: Failed to create RegexSet: {}", e);
+                        None
+                    }
+                };
+            groups
+                .insert(syslog_identifier, RegexConfGroup { confs, regex_set });
+        }
+        RegexConfSet { groups }
+    }
+
+    pub fn has_syslog_identifier(&self, syslog_identifier: &str) -> bool {
+        self.groups.contains_key(syslog_identifier)
+    }
+
+    // Return regex confs matching specified message, in the original order.
+    pub fn matches(
+        &self,
+        syslog_identifier: &str,
+        msg: &str,
+    ) -> Vec<&RegexConf> {
+        let group = match self.groups.get(syslog_identifier) {
+            Some(g) => g,
+            None => return Vec::new(),
+        };
+        match group.regex_set {
+            Some(ref regex_set) => regex_set
+                .matches(msg)
+                .into_iter()
+                .map(|i| &group.confs[i])
+                .collect(),
+            None => group.confs.iter().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+// Daemons like dmeventd and lvm share the same log messages, hence one
+// build-in regex could be used for several syslog identifiers.
 pub struct RegexConfStr<'a> {
+    pub syslog_identifiers: &'a [&'a str],
     pub starts_with: Option<&'a str>,
     pub regex: &'a str,
     pub sub_system: &'a str,
@@ -35,21 +106,27 @@ pub struct RegexConfStr<'a> {
 }
 
 impl<'a> RegexConfStr<'a> {
-    pub fn to_regex_conf(&self) -> RegexConf {
-        RegexConf {
-            starts_with: self.starts_with.map(|s| s.to_string()),
-            regex: Regex::new(self.regex).unwrap_or_else(|_| {
-                panic!("BUG: data.rs has invalid regex: {}", self.regex)
-            }),
-            // ^ We panic when hard-coded regex is not valid. It's developer's
-            // fault.
-            sub_system: self.sub_system
-                .parse()
-                .expect("BUG: data.rs has invalid sub_system"),
-            // ^ We panic when hard-coded sub_system is not valid. It's
-            // developer's fault.
-            event_type: self.event_type.to_string(),
-        }
+    pub fn to_regex_confs(&self) -> Vec<RegexConf> {
+        let regex = Regex::new(self.regex).unwrap_or_else(|_| {
+            panic!("BUG: data.rs has invalid regex: {}", self.regex)
+        });
+        // ^ We panic when hard-coded regex is not valid. It's developer's
+        // fault.
+        let sub_system = self.sub_system
+            .parse()
+            .expect("BUG: data.rs has invalid sub_system");
+        // ^ We panic when hard-coded sub_system is not valid. It's
+        // developer's fault.
+        self.syslog_identifiers
+            .iter()
+            .map(|i| RegexConf {
+                syslog_identifier: i.to_string(),
+                starts_with: self.starts_with.map(|s| s.to_string()),
+                regex: regex.clone(),
+                sub_system,
+                event_type: self.event_type.to_string(),
+            })
+            .collect()
     }
 }
 
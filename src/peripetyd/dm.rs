@@ -0,0 +1,91 @@
+use data::{EventType, ParserInfo};
+use peripety::{BlkInfo, StorageEvent, StorageSubSystem};
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+// LVM sub-LV role from its dm uuid suffix.
+fn owner_role(owner: &BlkInfo) -> Option<&'static str> {
+    if !owner.wwid.starts_with("LVM-") {
+        return None;
+    }
+    // Origin of snapshot, cache and writecache.
+    if owner.wwid.ends_with("-real")
+        || owner.wwid.ends_with("-corig")
+        || owner.wwid.ends_with("-wcorig")
+    {
+        Some("origin")
+    } else if owner.wwid.ends_with("-cow") {
+        Some("cow")
+    } else if owner.wwid.ends_with("-cdata") || owner.wwid.ends_with("-cvol") {
+        Some("fast")
+    } else if owner.wwid.ends_with("-tdata") || owner.wwid.ends_with("-vdata") {
+        Some("data")
+    } else if owner.wwid.ends_with("-tmeta") {
+        Some("metadata")
+    } else {
+        None
+    }
+}
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    event.msg = format!(
+        "{}, blk_wwid: '{}', blk_path: '{}'",
+        event.raw_msg, event.cur_blk_info.wwid, event.cur_blk_info.blk_path,
+    );
+    // Thin pool might be created by Stratis instead of LVM.
+    if event.cur_blk_info.wwid.starts_with("stratis-") {
+        event
+            .extension
+            .insert("pool_manager".to_string(), "Stratis".to_string());
+    } else if event.cur_blk_info.wwid.starts_with("LVM-") {
+        event
+            .extension
+            .insert("pool_manager".to_string(), "LVM".to_string());
+    }
+    // blk_info_get_dm() already walked the dm slaves, find out the origin
+    // and fast device of snapshot or cache.
+    for owner in &event.cur_blk_info.owners {
+        if let Some(role) = owner_role(owner) {
+            event
+                .extension
+                .insert(format!("{}_wwid", role), owner.wwid.clone());
+            event
+                .extension
+                .insert(format!("{}_blk_path", role), owner.blk_path.clone());
+            event.msg = format!(
+                "{}, {}_blk_path: '{}'",
+                event.msg, role, owner.blk_path
+            );
+        }
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("dm_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("dm_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "dm".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![
+            StorageSubSystem::DmSnapshot,
+            StorageSubSystem::DmCache,
+            StorageSubSystem::DmWriteCache,
+            StorageSubSystem::DmThin,
+            StorageSubSystem::Vdo,
+        ]),
+    }
+}
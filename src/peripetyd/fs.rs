@@ -69,6 +69,8 @@ pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
             StorageSubSystem::FsExt4,
             StorageSubSystem::FsXfs,
             StorageSubSystem::FsJbd2,
+            StorageSubSystem::FsGfs2,
+            StorageSubSystem::FsOcfs2,
         ]),
     }
 }
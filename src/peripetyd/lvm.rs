@@ -0,0 +1,43 @@
+use data::{EventType, ParserInfo};
+use peripety::{StorageEvent, StorageSubSystem};
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    // dmeventd logs device mapper name, collector has resolved it into
+    // block information of the thin pool or snapshot.
+    event.msg = format!(
+        "{}, blk_wwid: '{}', blk_path: '{}'",
+        event.raw_msg, event.cur_blk_info.wwid, event.cur_blk_info.blk_path,
+    );
+    if let Some(p) = event.extension.get("percent") {
+        event.msg = format!("{}, percent: '{}'", event.msg, p);
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("lvm_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("lvm_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "lvm".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![
+            StorageSubSystem::LvmThin,
+            StorageSubSystem::Lvm,
+        ]),
+    }
+}
@@ -13,9 +13,17 @@ mod buildin_regex;
 mod collector;
 mod conf;
 mod data;
+mod dm;
 mod fs;
+mod lvm;
 mod mpath;
+mod netblk;
+mod nvme;
+mod pcie;
 mod scsi;
+mod smart;
+mod swap;
+mod uevent;
 
 use chrono::{Local, SecondsFormat};
 use conf::ConfMain;
@@ -175,6 +183,8 @@ fn main() {
     let (daemon_conf_send, daemon_conf_recv) = mpsc::channel();
     let mut parsers: Vec<ParserInfo> = Vec::new();
     let mut dump_blk_info = true;
+    let mut max_catch_up_age = collector::DEFAULT_MAX_CATCH_UP_AGE;
+    let mut backfill_boot = false;
 
     let mut daemon_conf = None;
     let mut collector_conf = None;
@@ -182,6 +192,12 @@ fn main() {
         if c.main.dump_blk_info_at_start == Some(false) {
             dump_blk_info = false;
         }
+        if let Some(a) = c.main.max_catch_up_age {
+            max_catch_up_age = a;
+        }
+        if c.main.backfill_current_boot == Some(true) {
+            backfill_boot = true;
+        }
         daemon_conf = Some(c.main);
         collector_conf = Some(c.collector);
     }
@@ -206,6 +222,13 @@ fn main() {
     parsers.push(mpath::parser_start(notifier_send.clone()));
     parsers.push(scsi::parser_start(notifier_send.clone()));
     parsers.push(fs::parser_start(notifier_send.clone()));
+    parsers.push(smart::parser_start(notifier_send.clone()));
+    parsers.push(lvm::parser_start(notifier_send.clone()));
+    parsers.push(dm::parser_start(notifier_send.clone()));
+    parsers.push(netblk::parser_start(notifier_send.clone()));
+    parsers.push(swap::parser_start(notifier_send.clone()));
+    parsers.push(pcie::parser_start(notifier_send.clone()));
+    parsers.push(nvme::parser_start(notifier_send.clone()));
 
     let parsers_clone = parsers.clone();
 
@@ -233,18 +256,34 @@ fn main() {
     sleep(Duration::from_secs(1));
 
     // 4. Start collector thread
+    // Send config before collector starts, so that user regex is used when
+    // catching up logs generated while daemon was not running.
+    if let Some(c) = collector_conf {
+        conf_send
+            .send(c)
+            .expect("Failed to send config to collector");
+    }
+
     Builder::new()
         .name("collector".into())
         .spawn(move || {
-            collector::new(&collector_send, &conf_recv);
+            collector::new(
+                &collector_send,
+                &conf_recv,
+                max_catch_up_age,
+                backfill_boot,
+            );
         })
         .expect("Failed to start 'collector' thread");
 
-    if let Some(c) = collector_conf {
-        conf_send
-            .send(c)
-            .expect("Failed to send config to collector");
-    }
+    // 5. Start uevent thread for block add, remove and change.
+    let uevent_send = notifier_send.clone();
+    Builder::new()
+        .name("uevent".into())
+        .spawn(move || {
+            uevent::new(&uevent_send);
+        })
+        .expect("Failed to start 'uevent' thread");
 
     if let Some(c) = daemon_conf {
         daemon_conf_send
@@ -324,7 +363,8 @@ fn gethostname() -> String {
 
 fn dump_blk_infos(notifier_send: &Sender<StorageEvent>) {
     let blk_infos = BlkInfo::list().expect("Failed to query existing blocks");
-    for blk_info in blk_infos {
+    for mut blk_info in blk_infos {
+        blk_info.load_attributes();
         let msg = match blk_info.mount_point {
             Some(ref mount_point) => format!(
                 "Found block '{}' '{}' mounted at '{}'",
@@ -32,7 +32,7 @@ fn get_dm_name(dev_path: &str) -> Option<String> {
 }
 
 //TODO(Gris Ge): Maybe we should be save iscsi/fc data into BlkInfo::new().
-fn iscsi_session_id_of_host(host_id: &str) -> Option<String> {
+pub fn iscsi_session_id_of_host(host_id: &str) -> Option<String> {
     let path = format!("/sys/class/iscsi_host/host{}", host_id);
     let p = match fs::read_link(&path) {
         Ok(l) => match l.to_str() {
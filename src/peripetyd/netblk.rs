@@ -0,0 +1,49 @@
+use data::{EventType, ParserInfo};
+use peripety::{StorageEvent, StorageSubSystem};
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    // RBD: "<cluster_fsid>,<pool>,<image>,<snap>".
+    // NBD: backend identifier set by nbd client.
+    let transport_id = event.cur_blk_info.transport_id.clone();
+    event.msg = format!(
+        "{}, blk_wwid: '{}', blk_path: '{}'",
+        event.raw_msg, event.cur_blk_info.wwid, event.cur_blk_info.blk_path,
+    );
+    if !transport_id.is_empty() {
+        event.msg = format!("{}, transport_id: '{}'", event.msg, transport_id);
+        event
+            .extension
+            .insert("transport_id".to_string(), transport_id);
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("netblk_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => {
+                println!("netblk_parser: Failed to receive event: {}", e)
+            }
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "netblk".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![
+            StorageSubSystem::Rbd,
+            StorageSubSystem::Nbd,
+        ]),
+    }
+}
@@ -0,0 +1,112 @@
+use data::{EventType, ParserInfo};
+use peripety::{StorageEvent, StorageSubSystem};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+// Return the native NVMe multipath head(like nvme0n1) of specified path
+// namespace(like nvme0c1n1).
+fn nvme_mpath_head_of_path(path_ns: &str) -> Option<String> {
+    for entry in fs::read_dir("/sys/block").ok()? {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        let link = format!("/sys/block/{}/multipath/{}", name, path_ns);
+        if Path::new(&link).exists() {
+            return Some(name);
+        }
+    }
+    None
+}
+
+// Return block paths of namespaces accessed via specified controller, path
+// namespace hidden by native NVMe multipath is replaced by its head.
+fn namespaces_of_ctrl(ctrl: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    let reg = Regex::new(&format!(r"^{}(c[0-9]+)?n[0-9]+$", ctrl))
+        .expect("BUG: Regex string should be valid");
+    let entries = match fs::read_dir(format!("/sys/class/nvme/{}", ctrl)) {
+        Ok(e) => e,
+        Err(_) => return ret,
+    };
+    for entry in entries {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        let is_path = match reg.captures(&name) {
+            Some(c) => c.get(1).is_some(),
+            None => continue,
+        };
+        let blk = if is_path {
+            match nvme_mpath_head_of_path(&name) {
+                Some(h) => h,
+                None => continue,
+            }
+        } else {
+            name
+        };
+        let blk_path = format!("/dev/{}", blk);
+        if !ret.contains(&blk_path) {
+            ret.push(blk_path);
+        }
+    }
+    ret.sort();
+    ret
+}
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    let ctrl = match event.cur_blk_info.blk_path.rsplit('/').next() {
+        Some(c) => c.to_string(),
+        None => return,
+    };
+    // Collector already resolved the controller, its transport_id is
+    // "transport,traddr,trsvcid,subsysnqn".
+    let transport_id = event.cur_blk_info.transport_id.clone();
+    let keys = ["transport", "traddr", "trsvcid", "subsysnqn"];
+    for (key, value) in keys.iter().zip(transport_id.splitn(4, ',')) {
+        // PCIe controller has no traddr and trsvcid.
+        if !value.is_empty() {
+            event.extension.insert(key.to_string(), value.to_string());
+        }
+    }
+    event.extension.insert(
+        "namespaces".to_string(),
+        namespaces_of_ctrl(&ctrl).join(","),
+    );
+
+    event.msg = format!("{}, controller: '{}'", event.raw_msg, ctrl);
+    for key in &["transport", "traddr", "trsvcid", "subsysnqn", "namespaces"]
+    {
+        if let Some(v) = event.extension.get(*key) {
+            event.msg = format!("{}, {}: '{}'", event.msg, key, v);
+        }
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("nvme_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("nvme_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "nvme".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![StorageSubSystem::Nvme]),
+    }
+}
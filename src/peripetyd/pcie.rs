@@ -0,0 +1,111 @@
+use data::{EventType, ParserInfo};
+use peripety::{BlkInfo, StorageEvent, StorageSubSystem};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+// Return names of entries in sysfs class folder(like /sys/class/scsi_host)
+// which are below specified PCI function.
+fn sysfs_class_entries_of_pci(class_dir: &str, pci_addr: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    let pci_dir = format!("/{}/", pci_addr);
+    let entries = match fs::read_dir(class_dir) {
+        Ok(e) => e,
+        Err(_) => return ret,
+    };
+    for entry in entries {
+        let name = match entry.map(|e| e.file_name().into_string()) {
+            Ok(Ok(n)) => n,
+            _ => continue,
+        };
+        let path = Path::new(class_dir).join(&name);
+        // Partition is included by its disk.
+        if path.join("partition").exists() {
+            continue;
+        }
+        if let Ok(p) = fs::read_link(&path) {
+            if let Some(p) = p.to_str() {
+                if p.contains(&pci_dir) {
+                    ret.push(name);
+                }
+            }
+        }
+    }
+    ret.sort();
+    ret
+}
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    let pci_addr = event.cur_blk_info.transport_id.clone();
+    event.msg = format!("{}, pci_address: '{}'", event.raw_msg, pci_addr);
+
+    let scsi_hosts =
+        sysfs_class_entries_of_pci("/sys/class/scsi_host", &pci_addr);
+    let nvme_ctrls = sysfs_class_entries_of_pci("/sys/class/nvme", &pci_addr);
+    let mut blk_paths = Vec::new();
+    let mut wwids = Vec::new();
+    for blk in sysfs_class_entries_of_pci("/sys/class/block", &pci_addr) {
+        match BlkInfo::new_skip_extra(&blk) {
+            Ok(b) => {
+                blk_paths.push(b.blk_path);
+                wwids.push(b.wwid);
+            }
+            Err(e) => println!("pcie_parser: {}", e),
+        }
+    }
+
+    if !scsi_hosts.is_empty() {
+        event.msg = format!(
+            "{}, scsi_hosts: '{}'",
+            event.msg,
+            scsi_hosts.join(",")
+        );
+        event
+            .extension
+            .insert("scsi_hosts".to_string(), scsi_hosts.join(","));
+    }
+    if !nvme_ctrls.is_empty() {
+        event.msg = format!(
+            "{}, nvme_ctrls: '{}'",
+            event.msg,
+            nvme_ctrls.join(",")
+        );
+        event
+            .extension
+            .insert("nvme_ctrls".to_string(), nvme_ctrls.join(","));
+    }
+    if !blk_paths.is_empty() {
+        event.msg =
+            format!("{}, blk_paths: '{}'", event.msg, blk_paths.join(","));
+        // Both lists are in the same order.
+        event
+            .extension
+            .insert("blk_paths".to_string(), blk_paths.join(","));
+        event.extension.insert("blk_wwids".to_string(), wwids.join(","));
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("pcie_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("pcie_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "pcie".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![StorageSubSystem::Pcie]),
+    }
+}
@@ -1,18 +1,137 @@
-use data::{EventType, ParserInfo};
-use peripety::{BlkInfo, StorageEvent, StorageSubSystem};
+use data::{EventType, ParserInfo, Sysfs};
+use mpath::iscsi_session_id_of_host;
+use peripety::{BlkInfo, BlkType, StorageEvent, StorageSubSystem};
+use std::fs;
+use std::path::Path;
 use std::sync::mpsc;
 use std::sync::mpsc::Sender;
 use std::thread::spawn;
 
+fn scsi_host_transport(host: &str) -> &'static str {
+    if Path::new(&format!("/sys/class/iscsi_host/{}", host)).exists() {
+        "iSCSI"
+    } else if Path::new(&format!("/sys/class/fc_host/{}", host)).exists() {
+        "FC"
+    } else if Path::new(&format!("/sys/class/sas_host/{}", host)).exists() {
+        "SAS"
+    } else {
+        "unknown"
+    }
+}
+
+// The transport identity of local SCSI initiator used for persistent
+// reservation registration: iSCSI initiator name or FC host WWPN.
+fn initiator_transport_id(scsi_id: &str) -> Option<String> {
+    let host_id = Sysfs::scsi_host_id_of_scsi_id(scsi_id)?;
+    let iscsi_host_dir = format!("/sys/class/iscsi_host/host{}", host_id);
+    if Path::new(&iscsi_host_dir).exists() {
+        let sid = iscsi_session_id_of_host(&host_id)?;
+        return Some(Sysfs::read(&format!(
+            "/sys/class/iscsi_session/session{}/initiatorname",
+            sid
+        )));
+    }
+    let fc_host_dir = format!("/sys/class/fc_host/host{}", host_id);
+    if Path::new(&fc_host_dir).exists() {
+        return Some(Sysfs::read(&format!("{}/port_name", fc_host_dir)));
+    }
+    None
+}
+
+// Return scsi_id(h:c:t:l) of all disks behind specified SCSI host.
+fn scsi_ids_of_host(host: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    let host_id = &host["host".len()..];
+    if let Ok(entries) = fs::read_dir("/sys/class/scsi_disk") {
+        for entry in entries {
+            if let Ok(Ok(name)) = entry.map(|e| e.file_name().into_string()) {
+                if name.split(':').next() == Some(host_id) {
+                    ret.push(name);
+                }
+            }
+        }
+    }
+    ret.sort();
+    ret
+}
+
+// SCSI host event is fanned out to every disk behind that host, all sharing
+// the same event ID, so host reset is shown in the timeline of each disk.
+fn parse_host_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let host = match event.cur_blk_info.blk_path.rsplit('/').next() {
+        Some(h) => h.to_string(),
+        None => return,
+    };
+    let mut event = event.clone();
+    event.extension.insert(
+        "driver_name".to_string(),
+        Sysfs::read(&format!("/sys/class/scsi_host/{}/proc_name", host)),
+    );
+    event.extension.insert(
+        "transport".to_string(),
+        scsi_host_transport(&host).to_string(),
+    );
+    event.extension.insert("scsi_host".to_string(), host.clone());
+
+    let mut disk_events = Vec::new();
+    let mut disks = Vec::new();
+    let mut mpaths = Vec::new();
+    for scsi_id in scsi_ids_of_host(&host) {
+        let cur_blk_info = match BlkInfo::new_skip_extra(&scsi_id) {
+            Ok(b) => b,
+            Err(e) => {
+                println!("scsi_parser: {}", e);
+                continue;
+            }
+        };
+        let hierarchy_blk_info = match BlkInfo::new_hierarchy(&scsi_id) {
+            Ok(b) => b,
+            Err(_) => cur_blk_info.clone(),
+        };
+        disks.push(cur_blk_info.blk_path.clone());
+        if hierarchy_blk_info.blk_type == BlkType::DmMultipath
+            && !mpaths.contains(&hierarchy_blk_info.blk_path)
+        {
+            mpaths.push(hierarchy_blk_info.blk_path.clone());
+        }
+        let mut disk_event = event.clone();
+        disk_event.kdev = scsi_id;
+        disk_event.cur_blk_info = cur_blk_info;
+        disk_event.hierarchy_blk_info = hierarchy_blk_info;
+        disk_events.push(disk_event);
+    }
+    event.extension.insert("disks".to_string(), disks.join(","));
+    event.extension.insert("mpaths".to_string(), mpaths.join(","));
+
+    // No disk behind this host, still report the host event.
+    if disk_events.is_empty() {
+        disk_events.push(event.clone());
+    }
+    for mut disk_event in disk_events {
+        disk_event.extension = event.extension.clone();
+        disk_event.msg = format!(
+            "{}, wwid: '{}', driver: '{}', transport: '{}', disks: '{}', \
+             mpaths: '{}'",
+            event.raw_msg,
+            disk_event.cur_blk_info.wwid,
+            event.extension["driver_name"],
+            event.extension["transport"],
+            event.extension["disks"],
+            event.extension["mpaths"],
+        );
+        if let Err(e) = sender.send(disk_event) {
+            println!("scsi_parser: Failed to send event: {}", e);
+        }
+    }
+}
+
 fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
-    let mut kdev: &str = &event.kdev;
-    if event.kdev.starts_with("+scsi:host") {
+    // Collector has already decoded SCSI disk kernel device into scsi_id.
+    if event.cur_blk_info.blk_type == BlkType::ScsiHost {
+        parse_host_event(event, sender);
         return;
     }
-    if event.kdev.starts_with("+scsi:") {
-        kdev = &event.kdev["+scsi:".len()..];
-    }
-    match BlkInfo::new_skip_extra(kdev) {
+    match BlkInfo::new_skip_extra(&event.kdev) {
         Ok(b) => {
             let mut event = event.clone();
             event.blk_info = b;
@@ -33,6 +152,21 @@ fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
             }
             event.msg =
                 format!("{}, wwid: '{}'", event.raw_msg, event.blk_info.wwid);
+            if event.event_type == "SCSI_RESERVATION_CONFLICT"
+                && event.hierarchy_blk_info.blk_type == BlkType::DmMultipath
+            {
+                event.extension.insert(
+                    "mpath".to_string(),
+                    event.hierarchy_blk_info.blk_path.clone(),
+                );
+                if let Some(i) = initiator_transport_id(&event.kdev) {
+                    event.msg = format!(
+                        "{}, mpath: '{}', initiator: '{}'",
+                        event.msg, event.hierarchy_blk_info.blk_path, i
+                    );
+                    event.extension.insert("initiator".to_string(), i);
+                }
+            }
             if let Err(e) = sender.send(event) {
                 println!("scsi_parser: Failed to send event: {}", e);
             }
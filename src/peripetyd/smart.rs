@@ -0,0 +1,73 @@
+use data::{EventType, ParserInfo};
+use peripety::{StorageEvent, StorageSubSystem};
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    // SMART data is about the disk itself, collector has already resolved
+    // the device path logged by smartd into block information.
+    if event.cur_blk_info.wwid.is_empty() {
+        println!(
+            "smart_parser: Failed to find disk WWID of '{}'",
+            event.kdev
+        );
+        return;
+    }
+    let mut event = event.clone();
+    // smartd does not log the worst value and threshold of the attribute,
+    // only include what we got.
+    let attr_msg = match (
+        event.extension.get("attribute_id"),
+        event.extension.get("name"),
+    ) {
+        (Some(id), Some(name)) => format!(", attribute: {} {}", id, name),
+        _ => String::new(),
+    };
+    let mut value_msg = match (
+        event.extension.get("previous_value"),
+        event.extension.get("value"),
+    ) {
+        (Some(p), Some(v)) => format!(", value: {} -> {}", p, v),
+        _ => String::new(),
+    };
+    if let (Some(p), Some(v)) = (
+        event.extension.get("previous_raw_value"),
+        event.extension.get("raw_value"),
+    ) {
+        value_msg = format!("{}, raw value: {} -> {}", value_msg, p, v);
+    }
+    event.msg = format!(
+        "SMART {} on '{}', wwid: '{}'{}{}",
+        if event.event_type == "SMART_DISK_FAIL" {
+            "failure"
+        } else {
+            "attribute change"
+        },
+        event.cur_blk_info.blk_path,
+        event.cur_blk_info.wwid,
+        attr_msg,
+        value_msg
+    );
+    if let Err(e) = sender.send(event) {
+        println!("smart_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("smart_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "smart".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![StorageSubSystem::Smart]),
+    }
+}
@@ -0,0 +1,86 @@
+use data::{EventType, ParserInfo};
+use peripety::{BlkInfo, StorageEvent, StorageSubSystem};
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+use std::thread::spawn;
+
+// Find active swap in /proc/swaps using specified block, return swap path
+// and type("partition" or "file").
+fn find_active_swap(major_minor: &str) -> Option<(String, String)> {
+    let mut contents = String::new();
+    fs::File::open("/proc/swaps")
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .ok()?;
+    // Skip the header line.
+    for line in contents.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 2 {
+            continue;
+        }
+        let metadata = match fs::metadata(fields[0]) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        // Swap file is reported using the block holding the file system.
+        let dev = if fields[1] == "file" {
+            metadata.dev()
+        } else {
+            metadata.rdev()
+        };
+        if BlkInfo::dev_to_major_minor(dev) == major_minor {
+            return Some((fields[0].to_string(), fields[1].to_string()));
+        }
+    }
+    None
+}
+
+fn parse_event(event: &StorageEvent, sender: &Sender<StorageEvent>) {
+    let mut event = event.clone();
+    event.msg = format!(
+        "{}, blk_wwid: '{}', blk_path: '{}'",
+        event.raw_msg, event.cur_blk_info.wwid, event.cur_blk_info.blk_path,
+    );
+    match find_active_swap(&event.kdev) {
+        Some((swap_path, swap_type)) => {
+            event.msg = format!(
+                "{}, active swap {}: '{}'",
+                event.msg, swap_type, swap_path
+            );
+            event
+                .extension
+                .insert("swap_active".to_string(), "true".to_string());
+            event.extension.insert("swap_path".to_string(), swap_path);
+            event.extension.insert("swap_type".to_string(), swap_type);
+        }
+        None => {
+            event
+                .extension
+                .insert("swap_active".to_string(), "false".to_string());
+        }
+    }
+
+    if let Err(e) = sender.send(event) {
+        println!("swap_parser: Failed to send event: {}", e);
+    }
+}
+
+pub fn parser_start(sender: Sender<StorageEvent>) -> ParserInfo {
+    let (event_in_sender, event_in_recver) = mpsc::channel();
+
+    spawn(move || loop {
+        match event_in_recver.recv() {
+            Ok(event) => parse_event(&event, &sender),
+            Err(e) => println!("swap_parser: Failed to receive event: {}", e),
+        }
+    });
+
+    ParserInfo {
+        sender: event_in_sender,
+        name: "swap".to_string(),
+        filter_event_type: vec![EventType::Raw],
+        filter_event_subsys: Some(vec![StorageSubSystem::Swap]),
+    }
+}
@@ -0,0 +1,296 @@
+// Listen on kernel uevent netlink socket for block and SCSI device add, remove
+// and change. Kernel logs are inconsistent on these actions.
+//
+// Block information is cached when device is added or changed, so that removal
+// event can still report WWID after sysfs folder is gone. Mount point is
+// refreshed at removal via major:minor, as file system is commonly mounted
+// after device added.
+
+use chrono::{Local, SecondsFormat};
+use libc;
+use peripety::{
+    BlkInfo, LogSeverity, PeripetyError, StorageEvent, StorageSubSystem,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::mem;
+use std::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use gethostname;
+
+// The kernel multicast group of netlink uevent.
+const UEVENT_KERNEL_GROUP: u32 = 1;
+// Kernel UEVENT_BUFFER_SIZE is 2048.
+const UEVENT_BUFFER_SIZE: usize = 8192;
+// Uevent properties explaining why device changed, saved into event extension.
+const UEVENT_EXTENSION_KEYS: &[&str] =
+    &["SDEV_UA", "SDEV_MEDIA_CHANGE", "DISK_MEDIA_CHANGE", "RESIZE"];
+
+// Block information of current block and its hierarchy.
+type CachedBlkInfo = (BlkInfo, BlkInfo);
+
+#[derive(Debug)]
+struct Uevent {
+    action: String,
+    env: HashMap<String, String>,
+}
+
+impl Uevent {
+    // Kernel uevent is "action@devpath\0KEY=VALUE\0KEY=VALUE\0...".
+    fn parse(buff: &[u8]) -> Option<Uevent> {
+        let mut lines = buff.split(|c| *c == 0).filter(|l| !l.is_empty());
+        let header = String::from_utf8_lossy(lines.next()?);
+        // Message re-broadcasted by udevd starts with "libudev", we only
+        // subscribe kernel group, but just in case.
+        if !header.contains('@') {
+            return None;
+        }
+        let action = header.splitn(2, '@').next()?.to_string();
+        let mut env = HashMap::new();
+        for line in lines {
+            let line = String::from_utf8_lossy(line);
+            let mut kv = line.splitn(2, '=');
+            if let (Some(k), Some(v)) = (kv.next(), kv.next()) {
+                env.insert(k.to_string(), v.to_string());
+            }
+        }
+        Some(Uevent { action, env })
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.env.get(key).map(|s| s.as_str())
+    }
+
+    // Return block name(like 'sda') or SCSI ID(like '6:0:0:1').
+    fn kdev(&self) -> Option<String> {
+        match self.get("SUBSYSTEM")? {
+            "block" => self.get("DEVNAME").map(|s| s.to_string()),
+            "scsi" => {
+                if self.get("DEVTYPE") != Some("scsi_device") {
+                    return None;
+                }
+                let dev_path = self.get("DEVPATH")?;
+                dev_path.rsplit('/').next().map(|s| s.to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn open_uevent_socket() -> Option<i32> {
+    unsafe {
+        let fd = libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_KOBJECT_UEVENT,
+        );
+        if fd < 0 {
+            return None;
+        }
+        let mut addr: libc::sockaddr_nl = mem::zeroed();
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = UEVENT_KERNEL_GROUP;
+        if libc::bind(
+            fd,
+            &addr as *const _ as *const libc::sockaddr,
+            mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        ) != 0
+        {
+            libc::close(fd);
+            return None;
+        }
+        Some(fd)
+    }
+}
+
+fn get_blk_info(kdev: &str) -> Result<CachedBlkInfo, PeripetyError> {
+    Ok((BlkInfo::new_skip_extra(kdev)?, BlkInfo::new_hierarchy(kdev)?))
+}
+
+fn new_event(
+    event_type: &str,
+    severity: LogSeverity,
+    kdev: &str,
+    blk_infos: CachedBlkInfo,
+) -> StorageEvent {
+    let (cur_blk_info, blk_info) = blk_infos;
+    let msg = match blk_info.mount_point {
+        Some(ref mount_point) => format!(
+            "Block '{}' '{}' mounted at '{}' {}",
+            &cur_blk_info.blk_path,
+            &cur_blk_info.wwid,
+            mount_point,
+            event_type_to_action(event_type)
+        ),
+        None => format!(
+            "Block '{}' '{}' {}",
+            &cur_blk_info.blk_path,
+            &cur_blk_info.wwid,
+            event_type_to_action(event_type)
+        ),
+    };
+    let mut se: StorageEvent = Default::default();
+    se.hostname = gethostname();
+    se.severity = severity;
+    se.sub_system = StorageSubSystem::Block;
+    se.timestamp = Local::now().to_rfc3339_opts(SecondsFormat::Micros, false);
+    se.event_id = Uuid::new_v4().to_string();
+    se.event_type = event_type.to_string();
+    se.kdev = kdev.to_string();
+    se.cur_blk_info = cur_blk_info;
+    se.hierarchy_blk_info = blk_info;
+    se.msg = msg.clone();
+    se.raw_msg = msg;
+    se
+}
+
+fn event_type_to_action(event_type: &str) -> &'static str {
+    match event_type {
+        "BLK_ADDED" => "added",
+        "BLK_REMOVED" => "removed",
+        _ => "changed",
+    }
+}
+
+fn handle_uevent(
+    uevent: &Uevent,
+    cache: &mut HashMap<String, CachedBlkInfo>,
+    sender: &Sender<StorageEvent>,
+) {
+    let kdev = match uevent.kdev() {
+        Some(k) => k,
+        None => return,
+    };
+    let is_scsi = uevent.get("SUBSYSTEM") == Some("scsi");
+    let mut event = match uevent.action.as_ref() {
+        "add" | "change" => {
+            let event_type = if uevent.action == "add" {
+                "BLK_ADDED"
+            } else {
+                "BLK_CHANGED"
+            };
+            let blk_infos = match get_blk_info(&kdev) {
+                Ok(b) => b,
+                Err(e) => {
+                    // SCSI device has no block device yet when added, the
+                    // block uevent will follow.
+                    if !is_scsi {
+                        println!("uevent: {}", e);
+                    }
+                    return;
+                }
+            };
+            // Cache is keyed by block name only, so removal of a SCSI disk
+            // is reported once by its block.
+            if !is_scsi {
+                cache.insert(kdev.clone(), blk_infos.clone());
+            }
+            new_event(event_type, LogSeverity::Info, &kdev, blk_infos)
+        }
+        "remove" => {
+            let (cur_blk_info, mut blk_info) = match cache.remove(&kdev) {
+                Some(b) => b,
+                None => {
+                    // SCSI device removal is reported by block removal.
+                    if !is_scsi {
+                        println!(
+                            "uevent: No cached block information of {}",
+                            kdev
+                        );
+                    }
+                    return;
+                }
+            };
+            if let (Some(major), Some(minor)) =
+                (uevent.get("MAJOR"), uevent.get("MINOR"))
+            {
+                let major_minor = format!("{}:{}", major, minor);
+                if let Some(m) =
+                    BlkInfo::get_mount_point_of_major_minor(&major_minor)
+                {
+                    blk_info.mount_point = Some(m);
+                }
+            }
+            new_event(
+                "BLK_REMOVED",
+                LogSeverity::Notice,
+                &kdev,
+                (cur_blk_info, blk_info),
+            )
+        }
+        _ => return,
+    };
+    for key in UEVENT_EXTENSION_KEYS {
+        if let Some(v) = uevent.get(key) {
+            event.extension.insert(key.to_lowercase(), v.to_string());
+        }
+    }
+    if let Err(e) = sender.send(event) {
+        println!("uevent: Failed to send event: {}", e);
+    }
+}
+
+pub fn new(sender: &Sender<StorageEvent>) {
+    let fd = match open_uevent_socket() {
+        Some(fd) => fd,
+        None => {
+            println!("uevent: Failed to open netlink uevent socket");
+            return;
+        }
+    };
+
+    // Cache existing blocks, so we can report their removal.
+    let mut cache: HashMap<String, CachedBlkInfo> = HashMap::new();
+    match fs::read_dir("/sys/class/block") {
+        Ok(entries) => {
+            for entry in entries {
+                let name = match entry.map(|e| e.file_name().into_string()) {
+                    Ok(Ok(n)) => n,
+                    _ => continue,
+                };
+                // Unsupported block types are silently ignored.
+                if let Ok(b) = get_blk_info(&name) {
+                    cache.insert(name, b);
+                }
+            }
+        }
+        Err(e) => {
+            println!("uevent: Failed to read dir /sys/class/block: {}", e)
+        }
+    }
+
+    let mut buff = vec![0u8; UEVENT_BUFFER_SIZE];
+    loop {
+        let len = unsafe {
+            libc::recv(
+                fd,
+                buff.as_mut_ptr() as *mut libc::c_void,
+                buff.len(),
+                0,
+            )
+        };
+        if len < 0 {
+            let e = io::Error::last_os_error();
+            match e.raw_os_error() {
+                // ENOBUFS means kernel dropped uevents as socket buffer is
+                // full, cache might be outdated but we can still go on.
+                Some(libc::EINTR) | Some(libc::ENOBUFS) => continue,
+                _ => {
+                    println!(
+                        "uevent: Failed to receive from netlink socket: {}",
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+        if let Some(uevent) = Uevent::parse(&buff[..len as usize]) {
+            handle_uevent(&uevent, &mut cache, sender);
+        }
+    }
+    unsafe {
+        libc::close(fd);
+    }
+}
@@ -41,7 +41,9 @@ extern crate peripety;
 use chrono::{DateTime, Datelike, Duration, Local, TimeZone};
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use nix::sys::select::FdSet;
-use peripety::{BlkInfo, LogSeverity, StorageEvent, StorageSubSystem};
+use peripety::{
+    BlkGraph, BlkInfo, LogSeverity, StorageEvent, StorageSubSystem,
+};
 use std::ffi::CStr;
 use std::io::{self, Write};
 use std::os::unix::io::AsRawFd;
@@ -391,6 +393,30 @@ fn to_stdout_blk_info(
                 None => "",
             };
             to_stdout!("{}mount_point  : {}", prefix, mp);
+            let mut keys: Vec<&String> = i.extension.keys().collect();
+            keys.sort();
+            for key in keys {
+                to_stdout!("{}{:<13}: {}", prefix, key, i.extension[key]);
+            }
+            if let Some(ref a) = i.attributes {
+                to_stdout!("{}size         : {}", prefix, a.size);
+                to_stdout!("{}vendor       : {}", prefix, a.vendor);
+                to_stdout!("{}model        : {}", prefix, a.model);
+                to_stdout!("{}revision     : {}", prefix, a.revision);
+                to_stdout!("{}serial       : {}", prefix, a.serial);
+                to_stdout!("{}rotational   : {}", prefix, a.rotational);
+                to_stdout!(
+                    "{}log_blk_size : {}",
+                    prefix,
+                    a.logical_block_size
+                );
+                to_stdout!(
+                    "{}phy_blk_size : {}",
+                    prefix,
+                    a.physical_block_size
+                );
+                to_stdout!("{}zoned        : {}", prefix, a.zoned);
+            }
         }
         if prefix == "" && !i.owners.is_empty() {
             if !is_simple {
@@ -451,8 +477,11 @@ fn get_blk_info(blk: &str) -> Option<BlkInfo> {
     None
 }
 
-fn handle_info(blk: &str, is_json: bool) {
-    if let Some(i) = get_blk_info(blk) {
+fn handle_info(blk: &str, is_json: bool, is_detail: bool) {
+    if let Some(mut i) = get_blk_info(blk) {
+        if is_detail {
+            i.load_attributes();
+        }
         to_stdout_blk_info(&i, is_json, false, "");
     }
 }
@@ -461,7 +490,10 @@ fn handle_info(blk: &str, is_json: bool) {
 fn handle_list(is_json: bool, is_simple: bool) {
     match BlkInfo::list() {
         Ok(blk_infos) => {
-            for blk_info in blk_infos {
+            for mut blk_info in blk_infos {
+                if !is_simple {
+                    blk_info.load_attributes();
+                }
                 to_stdout_blk_info(&blk_info, is_json, is_simple, "");
                 to_stdout!("");
             }
@@ -472,6 +504,60 @@ fn handle_list(is_json: bool, is_simple: bool) {
     }
 }
 
+#[allow(unused_must_use)]
+fn to_stdout_blk_graph_node(graph: &BlkGraph, blk_name: &str, prefix: &str) {
+    if let Some(node) = graph.nodes.get(blk_name) {
+        to_stdout!(
+            "{}{} : {} : {}",
+            prefix,
+            node.blk_name,
+            node.major_minor,
+            node.blk_path
+        );
+        for slave in &node.slaves {
+            to_stdout_blk_graph_node(graph, slave, &format!("{}  ", prefix));
+        }
+    }
+}
+
+#[allow(unused_must_use)]
+fn handle_graph(blk: Option<&str>, is_json: bool) {
+    let mut graph = match BlkGraph::new() {
+        Ok(g) => g,
+        Err(e) => {
+            quit_with_msg(&format!("Failed to query block graph: {}", e));
+            return;
+        }
+    };
+    if let Some(blk) = blk {
+        // Only keep the blocks related to specified block.
+        let blk_name = match graph.blk_name_of(blk) {
+            Some(n) => n,
+            None => {
+                quit_with_msg("Specified block not found");
+                return;
+            }
+        };
+        let mut related = graph.ancestors(&blk_name);
+        related.append(&mut graph.descendants(&blk_name));
+        related.push(blk_name);
+        graph.nodes.retain(|k, _| related.contains(k));
+    }
+    if is_json {
+        to_stdout!(
+            "{}",
+            graph.to_json_string_pretty().expect("BUG: handle_graph()")
+        );
+        return;
+    }
+    // Print from top level blocks down to the blocks they are built on.
+    for node in graph.nodes.values() {
+        if node.holders.iter().all(|h| !graph.nodes.contains_key(h)) {
+            to_stdout_blk_graph_node(&graph, &node.blk_name, "");
+        }
+    }
+}
+
 fn check_permission() {
     let journal_group_name = "systemd-journal";
     if nix::unistd::geteuid() == nix::unistd::Uid::from_raw(0) {
@@ -572,6 +658,9 @@ fn main() {
                      block name, block path, symbolic link to block, \
                      uuid, wwid, or fs mount point'",
                 ))
+                .arg(Arg::from_usage(
+                    "-D 'Detailed output with size, vendor, model and etc'",
+                ))
                 .arg(&json_arg),
         )
         .subcommand(
@@ -580,6 +669,15 @@ fn main() {
                 .arg(Arg::from_usage("-D 'Detailed output'"))
                 .arg(&json_arg),
         )
+        .subcommand(
+            SubCommand::with_name("graph")
+                .about("Show holder and slave relationship of blocks")
+                .arg(Arg::from_usage(
+                    "[blk] 'Only show blocks related to specified block, \
+                     could be \'major:minor\', block name or block path'",
+                ))
+                .arg(&json_arg),
+        )
         .get_matches();
 
     if let Some(matches) = matches.subcommand_matches("monitor") {
@@ -603,7 +701,7 @@ fn main() {
     if let Some(matches) = matches.subcommand_matches("info") {
         let is_json = matches.is_present("J");
         match matches.value_of("blk") {
-            Some(s) => handle_info(s, is_json),
+            Some(s) => handle_info(s, is_json, matches.is_present("D")),
             None => quit_with_msg("Invalid 'blk' argument"),
         }
         exit(0);
@@ -618,6 +716,11 @@ fn main() {
         handle_list(is_json, is_simple);
         exit(0);
     }
+
+    if let Some(matches) = matches.subcommand_matches("graph") {
+        handle_graph(matches.value_of("blk"), matches.is_present("J"));
+        exit(0);
+    }
 }
 
 #[cfg(feature = "notify")]
@@ -153,6 +153,12 @@ extern "C" {
     ) -> c_int;
 
     fn sd_journal_seek_realtime_usec(j: *mut SdJournal, usec: u64) -> c_int;
+
+    fn sd_journal_get_cursor(j: *mut SdJournal, cursor: *mut *mut c_char)
+        -> c_int;
+
+    fn sd_journal_seek_cursor(j: *mut SdJournal, cursor: *const c_char)
+        -> c_int;
 }
 
 // Copied and pasted from https://github.com/rust-lang/rust/blob/master/src/libstd/sys/unix/os.rs
@@ -308,6 +314,7 @@ impl Journal {
                 rc,
             )));
         }
+        result.insert("__CURSOR".to_string(), self.get_cursor()?);
 
         Ok(result)
     }
@@ -414,6 +421,37 @@ impl Journal {
         Ok(())
     }
 
+    pub fn get_cursor(&mut self) -> Result<String, SdJournalError> {
+        let mut x: *mut c_char = ptr::null_mut();
+        let rc = unsafe { sd_journal_get_cursor(self.handle, &mut x) };
+        if rc < 0 {
+            return Err(SdJournalError::CError(ClibraryError::new(
+                String::from("Error on sd_journal_get_cursor"),
+                rc,
+            )));
+        }
+        let cursor =
+            String::from_utf8(unsafe { CStr::from_ptr(x) }.to_bytes().to_vec());
+        // The cursor string is allocated by libsystemd via malloc().
+        unsafe { libc::free(x as *mut c_void) };
+        Ok(cursor?)
+    }
+
+    // Next entry retrieved will be the one pointed by cursor or the closest
+    // one if that entry is not available any more.
+    pub fn seek_cursor(&mut self, cursor: &str) -> Result<(), SdJournalError> {
+        let cursor = CString::new(cursor)?;
+        let rc =
+            unsafe { sd_journal_seek_cursor(self.handle, cursor.as_ptr()) };
+        if rc < 0 {
+            return Err(SdJournalError::CError(ClibraryError::new(
+                String::from("Error on sd_journal_seek_cursor"),
+                rc,
+            )));
+        }
+        Ok(())
+    }
+
     pub fn seek_realtime_usec(
         &mut self,
         usec: u64,